@@ -1,45 +1,214 @@
 use crate::client::ReqwestClient;
-use crate::config::FbxConfig;
+use crate::config::{env_var, FbxConfig, FbxProfile, FbxSession, CONFIG_ENV, PROFILE_ENV};
+use crate::i18n::{self, Locale, Message};
+use crate::logging;
 use crate::models::args::Cli;
-use crate::models::freebox::authorization::AuthTokenRequest;
-use crate::services::api::FreeboxOSApi;
+use crate::models::exception::{ClientError, EXIT_INVALID_INPUT};
+use crate::models::freebox::authorization::{
+    AuthSessionPermissions, AuthSessionStartRequest, AuthTokenRequest, Permission,
+};
+use crate::models::freebox::version::{FreeboxMajorVersion, VersionResponse};
+use crate::services::api::{FreeboxOSApi, FreeboxOSApiCalls};
 use clap::Parser;
 use serde::Deserialize;
-use url::Url;
+use std::path::PathBuf;
 
 #[derive(Clone, Debug)]
 pub struct App {
     pub api: FreeboxOSApi,
-    pub config: FbxConfig,
+    /// Active profile, written back into `profiles` on save.
+    pub config: FbxProfile,
+    pub profile: String,
+    pub profiles: FbxConfig,
     pub client: ReqwestClient,
     pub cli: Cli,
 }
 
 impl Default for App {
     fn default() -> Self {
+        let env_path = env_var(CONFIG_ENV).map(PathBuf::from);
+        let profiles = FbxConfig::load(env_path.as_deref());
+        let profile = env_var(PROFILE_ENV).unwrap_or(profiles.current.clone());
+        i18n::init(
+            profiles
+                .profile(&profile)
+                .and_then(|profile| profile.pref.locale)
+                .or_else(Locale::from_env)
+                .unwrap_or_default(),
+        );
+        let cli = Cli::try_parse().unwrap_or_else(|error| {
+            let _ = error.print();
+            std::process::exit(match error.use_stderr() {
+                true => EXIT_INVALID_INPUT,
+                false => 0,
+            })
+        });
+        let profiles = match cli.config.is_some() && cli.config != env_path {
+            true => FbxConfig::load(cli.config.as_deref()),
+            false => profiles,
+        };
+        App::new(cli, profiles)
+    }
+}
+
+impl App {
+    /// App running the command line `cli`, on the profile it selects,
+    /// overridden by the environment.
+    pub fn new(cli: Cli, profiles: FbxConfig) -> Self {
+        let mut app = App::without_env(cli, profiles);
+        app.profiles.read_only = app.config.apply_env();
+        app
+    }
+
+    /// App running the command line `cli`, on the profile it selects, as
+    /// written in `profiles` whatever the environment says.
+    pub fn without_env(cli: Cli, profiles: FbxConfig) -> Self {
+        logging::init(cli.verbose, cli.quiet, cli.no_color);
+        let profile = cli.profile.clone().unwrap_or(profiles.current.clone());
+        let config = profiles.profile(&profile).cloned().unwrap_or_default();
+        let mut client = ReqwestClient::default();
+        client.set_timeout(cli.timeout);
+        client.set_retries(cli.retries);
         App {
             api: FreeboxOSApi,
-            config: FbxConfig::load(),
-            client: ReqwestClient::default(),
-            cli: Cli::parse(),
+            config,
+            profile,
+            profiles,
+            client,
+            cli,
         }
     }
-}
 
-impl App {
-    pub fn save(&self) {
-        self.config.save().expect("TODO: panic message")
+    /// Writes the profiles back, once their app tokens are in the secret
+    /// store. Nothing is written when the store fails, the token would
+    /// otherwise be lost or end up in the file.
+    pub fn save(&mut self) -> Result<(), ClientError> {
+        if self.profiles.read_only {
+            return Ok(());
+        }
+        self.profiles
+            .profiles
+            .insert(self.profile.clone(), self.config.clone());
+        if let Err(e) = self.profiles.store_secrets() {
+            return Err(ClientError::SecretStore(
+                Message::SecretStoreFailed(&format!("{:?}", e)).to_string(),
+            ));
+        }
+        self.profiles.save().map_err(|e| {
+            ClientError::UnknownError(
+                Message::ErrorConfigWrite(
+                    &self.profiles.location().display().to_string(),
+                    &e.to_string(),
+                )
+                .to_string(),
+            )
+        })
     }
 
-    pub async fn initialize(&mut self) {
+    pub async fn initialize(&mut self) -> Result<(), ClientError> {
+        if !self.profiles.profiles.is_empty() && self.profiles.profile(&self.profile).is_none() {
+            return Err(ClientError::InvalidArgument(
+                Message::ProfileUnknown(&self.profile).to_string(),
+            ));
+        }
         let pref = &self.config.pref;
-        let url = Url::parse(pref.base_url.as_str());
-        if let Ok(url) = url {
-            self.client.set_url(url.to_string());
-        } else {
+        if pref.is_https() {
             self.client
-                .set_url(format!("{}/api/{}", pref.base_url, pref.version));
+                .set_root_certificates(&root_certificates(pref.ca_file.as_deref())?)?;
+        }
+        self.client
+            .set_url(pref.url(), pref.api_base_url(), &pref.version);
+        Ok(())
+    }
+
+    /// Asks `/api_version` to the box, which is served at the root of the box
+    /// url rather than under the api path.
+    pub async fn box_version(&self) -> Result<VersionResponse, ClientError> {
+        let mut root = self.client.clone();
+        root.set_full_url(self.config.pref.url().trim_end_matches('/').to_string());
+        Ok(self.api.api_version(&root).await?)
+    }
+
+    /// Asks the box for its api version and path, and caches them in the
+    /// configuration.
+    pub async fn detect_api_version(&mut self) -> Result<(), ClientError> {
+        let version = self.box_version().await?;
+        self.config.pref.version = major_version(&version.api_version)?;
+        self.config.pref.api_base_url = Some(version.api_base_url);
+        self.initialize().await
+    }
+
+    /// Returns a valid session token, opening a new session with the app token
+    /// when the stored one has expired.
+    pub async fn session_token(&mut self) -> Result<String, ClientError> {
+        if self.config.pref.api_base_url.is_none() {
+            self.detect_api_version().await?;
+        }
+        if let Some(session) = &self.config.session {
+            let response = self
+                .api
+                .login(&self.client, Some(session.token_session.clone()))
+                .await?;
+            if response.result.logged_in {
+                return Ok(session.token_session.clone());
+            }
+        }
+        let (app_id, app_token) = match &self.config.app {
+            Some(app) => (
+                app.app_id.clone(),
+                self.profiles.app_token(&self.profile, app)?,
+            ),
+            None => (String::new(), None),
+        };
+        let app_token = app_token.ok_or(ClientError::CliNeedAuth(None))?;
+        let login_response = self.api.login(&self.client, None).await?;
+        let challenge = login_response
+            .result
+            .challenge
+            .ok_or(ClientError::RequestError(
+                Message::ErrorMissingChallenge.to_string(),
+            ))?;
+        let body = AuthSessionStartRequest::new(app_id, app_token, challenge).ok_or(
+            ClientError::RequestError(Message::ErrorSignChallenge.to_string()),
+        )?;
+        let response = self.api.start_session(&self.client, body).await?;
+        self.config.session = Some(FbxSession {
+            token_session: response.result.session_token.clone(),
+            created_at: chrono::Utc::now(),
+            permissions: Some(response.result.permissions),
+        });
+        Ok(response.result.session_token)
+    }
+
+    /// Same as [`App::session_token`], but fails early when the app lacks the
+    /// permission the command needs. A session opened before the permission
+    /// was granted in Freebox OS is replaced by a new one.
+    pub async fn session_token_for(
+        &mut self,
+        permission: Permission,
+    ) -> Result<String, ClientError> {
+        let session_token = self.session_token().await?;
+        match self.session_permissions() {
+            Some(permissions) if !permissions.has(permission) => {}
+            _ => return Ok(session_token),
         }
+        self.config.session = None;
+        let session_token = self.session_token().await?;
+        match self.session_permissions() {
+            Some(permissions) if !permissions.has(permission) => {
+                Err(ClientError::PermissionDenied(
+                    Message::AuthMissingPermission(permission.name()).to_string(),
+                ))
+            }
+            _ => Ok(session_token),
+        }
+    }
+
+    fn session_permissions(&self) -> Option<&AuthSessionPermissions> {
+        self.config
+            .session
+            .as_ref()
+            .and_then(|session| session.permissions.as_ref())
     }
 }
 
@@ -60,9 +229,82 @@ impl From<AuthAppConfig> for AuthTokenRequest {
     }
 }
 
+/// Freebox ECC and RSA root CAs, from the Freebox OS SDK documentation.
+const FREEBOX_ROOT_CAS: [&[u8]; 2] = [
+    include_bytes!("../certs/freebox_ecc_root_ca.pem"),
+    include_bytes!("../certs/freebox_root_ca.pem"),
+];
+const PEM_CERTIFICATE: &str = "-----BEGIN CERTIFICATE-----";
+
+/// Root CAs trusted over HTTPS: the PEM file given with `--ca-file`, or else
+/// the Freebox root CAs bundled in the binary.
+pub fn root_certificates(ca_file: Option<&str>) -> Result<Vec<u8>, ClientError> {
+    if let Some(ca_file) = ca_file {
+        return read_ca_file(ca_file);
+    }
+    let bundle = FREEBOX_ROOT_CAS.concat();
+    match String::from_utf8_lossy(&bundle).contains(PEM_CERTIFICATE) {
+        true => Ok(bundle),
+        false => Err(ClientError::InvalidArgument(
+            Message::HttpsCaFileRequired.to_string(),
+        )),
+    }
+}
+
+/// Major of the api version reported by the box, which must be valid.
+pub fn major_version(api_version: &str) -> Result<FreeboxMajorVersion, ClientError> {
+    FreeboxMajorVersion::from_api_version(api_version).ok_or_else(|| {
+        ClientError::RequestError(Message::ErrorInvalidApiVersion(api_version).to_string())
+    })
+}
+
+pub fn read_ca_file(ca_file: &str) -> Result<Vec<u8>, ClientError> {
+    std::fs::read(ca_file).map_err(|_| {
+        ClientError::InvalidArgument(Message::ErrorCaFileNotFound(ca_file).to_string())
+    })
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct ResponseResult<T> {
-    pub success: bool,
-    pub message: Option<String>,
     pub result: T,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::fake::FakeFreebox;
+    use crate::models::exception::ClientError;
+    use crate::models::freebox::version::FreeboxMajorVersion;
+    use crate::run;
+
+    #[tokio::test]
+    async fn detected_majors_are_kept() {
+        let fbx = FakeFreebox::start();
+        let mut version = FakeFreebox::fixture("api_version");
+        version["api_version"] = "12.0".into();
+        fbx.answer("GET", "/api_version/", 200, version);
+        let mut app = fbx.app(&["lcd", "get"]).await;
+
+        app.detect_api_version().await.unwrap();
+
+        assert_eq!(app.config.pref.version, FreeboxMajorVersion::Other(12));
+        assert_eq!(
+            app.client.fmt_url("/lcd/config"),
+            format!("{}api/v12/lcd/config", fbx.url)
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_majors_are_refused() {
+        let fbx = FakeFreebox::start();
+        let mut version = FakeFreebox::fixture("api_version");
+        version["api_version"] = "0.9".into();
+        fbx.answer("GET", "/api_version/", 200, version);
+        let mut app = fbx.app(&["lcd", "get"]).await;
+
+        let result = run(&mut app).await;
+
+        assert!(matches!(result, Err(ClientError::RequestError(_))));
+        assert!(app.config.pref.api_base_url.is_none());
+        assert!(fbx.sessions().is_empty());
+    }
+}
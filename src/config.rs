@@ -1,19 +1,293 @@
+use crate::i18n::{Locale, Message};
+use crate::info;
+use crate::models::freebox::authorization::AuthSessionPermissions;
 use crate::models::freebox::version::FreeboxMajorVersion;
+use argon2::Argon2;
+use base64::prelude::{Engine, BASE64_STANDARD};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
 use chrono::Utc;
+use inquire::Password;
 use serde::{Deserialize, Serialize};
+use std::cell::OnceCell;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 const APP_NAME: &str = "fbx";
 const CONFIG_NAME: &str = "main";
+const SECRETS_NAME: &str = "secrets";
+pub const PASSPHRASE_ENV: &str = "FBX_PASSPHRASE";
+pub const PROFILE_ENV: &str = "FBX_PROFILE";
+pub const CONFIG_ENV: &str = "FBX_CONFIG";
+pub const URL_ENV: &str = "FBX_URL";
+pub const APP_ID_ENV: &str = "FBX_APP_ID";
+pub const APP_TOKEN_ENV: &str = "FBX_APP_TOKEN";
+pub const API_VERSION_ENV: &str = "FBX_API_VERSION";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+pub const DEFAULT_PROFILE: &str = "default";
+const DEFAULT_API_BASE_URL: &str = "/api/";
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub enum FbxAppStatus {
     #[default]
     Pending,
+    /// Only kept in memory, it is moved to the secret store on save and
+    /// refuses to be serialized so the token never reaches the TOML file.
+    #[serde(skip_serializing)]
     Granted(String),
+    /// Granted, the app token lives in the secret store.
+    Stored,
+}
+
+/// Where app tokens are kept, they never end up in the TOML file.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub enum SecretBackend {
+    /// Secret Service on Linux, Keychain on macOS, Credential Manager on
+    /// Windows.
+    #[default]
+    #[serde(rename = "keyring")]
+    Keyring,
+    /// `secrets.toml` next to the configuration, encrypted with a passphrase.
+    #[serde(rename = "encrypted_file")]
+    EncryptedFile,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SecretError {
+    Unavailable(String),
+    InvalidPassphrase,
+    Corrupted,
+}
+
+pub trait SecretStore {
+    fn get(&self, key: &str) -> Result<Option<String>, SecretError>;
+    fn set(&self, key: &str, secret: &str) -> Result<(), SecretError>;
+    fn delete(&self, key: &str) -> Result<(), SecretError>;
+}
+
+pub struct KeyringStore;
+
+impl KeyringStore {
+    /// The Secret Service client blocks on its own runtime, so it can't be
+    /// called from a tokio worker.
+    fn with_entry<T: Send>(
+        key: &str,
+        f: impl FnOnce(keyring::Entry) -> Result<T, SecretError> + Send,
+    ) -> Result<T, SecretError> {
+        std::thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    let entry = keyring::Entry::new(APP_NAME, key)
+                        .map_err(|e| SecretError::Unavailable(e.to_string()))?;
+                    f(entry)
+                })
+                .join()
+                .unwrap_or_else(|_| Err(SecretError::Unavailable(APP_NAME.to_string())))
+        })
+    }
+}
+
+impl SecretStore for KeyringStore {
+    fn get(&self, key: &str) -> Result<Option<String>, SecretError> {
+        Self::with_entry(key, |entry| match entry.get_password() {
+            Ok(secret) => Ok(Some(secret)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(SecretError::Unavailable(e.to_string())),
+        })
+    }
+
+    fn set(&self, key: &str, secret: &str) -> Result<(), SecretError> {
+        Self::with_entry(key, |entry| {
+            entry
+                .set_password(secret)
+                .map_err(|e| SecretError::Unavailable(e.to_string()))
+        })
+    }
+
+    fn delete(&self, key: &str) -> Result<(), SecretError> {
+        Self::with_entry(key, |entry| match entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(SecretError::Unavailable(e.to_string())),
+        })
+    }
+}
+
+/// Fallback when no keyring is available: every secret is encrypted with
+/// ChaCha20-Poly1305, the key being derived from a passphrase with Argon2.
+pub struct EncryptedFileStore {
+    path: PathBuf,
+    passphrase: OnceCell<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct EncryptedSecrets {
+    #[serde(default)]
+    secrets: BTreeMap<String, String>,
+}
+
+impl EncryptedFileStore {
+    pub fn new(path: PathBuf) -> Self {
+        EncryptedFileStore {
+            path,
+            passphrase: OnceCell::new(),
+        }
+    }
+
+    /// Reads `FBX_PASSPHRASE`, or asks it once, with a confirmation when the
+    /// file is created.
+    fn passphrase(&self, confirm: bool) -> Result<&str, SecretError> {
+        if let Some(passphrase) = self.passphrase.get() {
+            return Ok(passphrase);
+        }
+        let passphrase = match std::env::var(PASSPHRASE_ENV) {
+            Ok(passphrase) => passphrase,
+            Err(_) => {
+                let message = Message::SecretPassphrase.to_string();
+                let prompt = Password::new(&message);
+                let prompt = if confirm {
+                    prompt
+                } else {
+                    prompt.without_confirmation()
+                };
+                prompt
+                    .prompt()
+                    .map_err(|e| SecretError::Unavailable(e.to_string()))?
+            }
+        };
+        Ok(self.passphrase.get_or_init(|| passphrase))
+    }
+
+    fn cipher(passphrase: &str, salt: &[u8]) -> Result<ChaCha20Poly1305, SecretError> {
+        let mut key = [0u8; 32];
+        Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+            .map_err(|e| SecretError::Unavailable(e.to_string()))?;
+        Ok(ChaCha20Poly1305::new(Key::from_slice(&key)))
+    }
+
+    fn encrypt(&self, secret: &str) -> Result<String, SecretError> {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let cipher = Self::cipher(self.passphrase(!self.path.exists())?, &salt)?;
+        let encrypted = cipher
+            .encrypt(&nonce, secret.as_bytes())
+            .map_err(|_| SecretError::Corrupted)?;
+        Ok(BASE64_STANDARD.encode([&salt[..], &nonce[..], &encrypted[..]].concat()))
+    }
+
+    fn decrypt(&self, secret: &str) -> Result<String, SecretError> {
+        let secret = BASE64_STANDARD
+            .decode(secret)
+            .map_err(|_| SecretError::Corrupted)?;
+        if secret.len() < SALT_LEN + NONCE_LEN {
+            return Err(SecretError::Corrupted);
+        }
+        let (salt, secret) = secret.split_at(SALT_LEN);
+        let (nonce, encrypted) = secret.split_at(NONCE_LEN);
+        let cipher = Self::cipher(self.passphrase(false)?, salt)?;
+        let decrypted = cipher
+            .decrypt(Nonce::from_slice(nonce), encrypted)
+            .map_err(|_| SecretError::InvalidPassphrase)?;
+        String::from_utf8(decrypted).map_err(|_| SecretError::Corrupted)
+    }
+
+    fn load(&self) -> Result<EncryptedSecrets, SecretError> {
+        if !self.path.exists() {
+            return Ok(EncryptedSecrets::default());
+        }
+        confy::load_path(&self.path).map_err(|_| SecretError::Corrupted)
+    }
+
+    fn store(&self, secrets: &EncryptedSecrets) -> Result<(), SecretError> {
+        confy::store_path(&self.path, secrets)
+            .map_err(|e| SecretError::Unavailable(e.to_string()))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&self.path, std::fs::Permissions::from_mode(0o600))
+                .map_err(|e| SecretError::Unavailable(e.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+impl SecretStore for EncryptedFileStore {
+    fn get(&self, key: &str) -> Result<Option<String>, SecretError> {
+        self.load()?
+            .secrets
+            .get(key)
+            .map(|secret| self.decrypt(secret))
+            .transpose()
+    }
+
+    fn set(&self, key: &str, secret: &str) -> Result<(), SecretError> {
+        let mut secrets = self.load()?;
+        // Every secret of the file must share the same passphrase.
+        if let Some(existing) = secrets.secrets.values().next() {
+            self.decrypt(existing)?;
+        }
+        secrets
+            .secrets
+            .insert(key.to_string(), self.encrypt(secret)?);
+        self.store(&secrets)
+    }
+
+    fn delete(&self, key: &str) -> Result<(), SecretError> {
+        let mut secrets = self.load()?;
+        if secrets.secrets.remove(key).is_some() {
+            self.store(&secrets)?;
+        }
+        Ok(())
+    }
+}
+
+/// Every known box, stored as `[profiles.<name>]` tables.
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FbxConfig {
+    /// Profile used when neither `--profile` nor `FBX_PROFILE` is given.
+    #[serde(default = "default_profile")]
+    pub current: String,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, FbxProfile>,
+    #[serde(default)]
+    pub secrets: SecretBackend,
+    /// File the configuration was loaded from, `None` for the default path.
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+    /// Set when the environment overrides the file, the configuration is then
+    /// never written back so that nothing coming from the environment leaks
+    /// into it.
+    #[serde(skip)]
+    pub read_only: bool,
+    /// Passphrase of the encrypted file, instead of `FBX_PASSPHRASE` or a
+    /// prompt.
+    #[serde(skip)]
+    pub passphrase: Option<String>,
+}
+
+fn default_profile() -> String {
+    DEFAULT_PROFILE.to_string()
+}
+
+impl Default for FbxConfig {
+    fn default() -> Self {
+        FbxConfig {
+            current: default_profile(),
+            profiles: BTreeMap::new(),
+            secrets: SecretBackend::default(),
+            path: None,
+            read_only: false,
+            passphrase: None,
+        }
+    }
+}
+
+/// App token, session and preferences of one box.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct FbxProfile {
     pub app: Option<FbxApp>,
     pub session: Option<FbxSession>,
     pub pref: FbxPreferences,
@@ -27,18 +301,45 @@ pub struct FbxApp {
     pub status: FbxAppStatus,
     pub created_at: chrono::DateTime<Utc>,
     pub authorized_at: Option<chrono::DateTime<Utc>>,
+    /// Store holding the app token, the preferred one of the configuration
+    /// when unset.
+    #[serde(default)]
+    pub secrets: Option<SecretBackend>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct FbxSession {
     pub token_session: String,
     pub created_at: chrono::DateTime<Utc>,
+    /// Permissions returned when the session was opened.
+    #[serde(default)]
+    pub permissions: Option<AuthSessionPermissions>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FbxPreferences {
     pub base_url: String,
     pub version: FreeboxMajorVersion,
+    /// Api path returned by `/api_version`, `None` until it has been detected.
+    #[serde(default)]
+    pub api_base_url: Option<String>,
+    #[serde(default)]
+    pub locale: Option<Locale>,
+    /// PEM bundle of the Freebox root certificates, the only ones trusted when
+    /// talking to the box over HTTPS.
+    #[serde(default)]
+    pub ca_file: Option<String>,
+    /// LAN address used before `fbx config https enable`, restored by
+    /// `fbx config https disable`.
+    #[serde(default)]
+    pub http_url: Option<String>,
+    /// Public HTTPS endpoint recorded by `fbx connection remote-api enable`.
+    #[serde(default)]
+    pub remote_url: Option<String>,
+    /// Targets `remote_url` instead of `base_url`, to reach the box from
+    /// outside its LAN.
+    #[serde(default)]
+    pub remote: bool,
 }
 
 impl Default for FbxPreferences {
@@ -46,21 +347,489 @@ impl Default for FbxPreferences {
         FbxPreferences {
             base_url: "http://mafreebox.freebox.fr".to_string(),
             version: FreeboxMajorVersion::default(),
+            api_base_url: None,
+            locale: None,
+            ca_file: None,
+            http_url: None,
+            remote_url: None,
+            remote: false,
+        }
+    }
+}
+
+impl FbxPreferences {
+    /// Changes the box url, the api version will be detected again on next use.
+    pub fn set_base_url(&mut self, base_url: String) {
+        self.base_url = base_url;
+        self.version = FreeboxMajorVersion::default();
+        self.api_base_url = None;
+    }
+
+    /// Url of the box, the public endpoint when remote mode is on.
+    pub fn url(&self) -> &str {
+        match (self.remote, &self.remote_url) {
+            (true, Some(remote_url)) => remote_url,
+            _ => &self.base_url,
         }
     }
+
+    pub fn is_https(&self) -> bool {
+        self.url().starts_with("https://")
+    }
+
+    pub fn api_base_url(&self) -> &str {
+        self.api_base_url.as_deref().unwrap_or(DEFAULT_API_BASE_URL)
+    }
 }
 
 impl FbxConfig {
-    pub fn load() -> Self {
-        confy::load(APP_NAME, CONFIG_NAME).unwrap_or(FbxConfig::default())
+    /// Loads the profiles from `path`, or from the default path, a missing
+    /// file is not created. A configuration written before profiles existed
+    /// becomes the `default` profile.
+    pub fn load(path: Option<&Path>) -> Self {
+        let file = Self::file(path);
+        let mut config = FbxConfig::default();
+        if file.exists() {
+            config = confy::load_path(&file).unwrap_or(FbxConfig::default());
+            if config.profiles.is_empty() {
+                if let Ok(legacy) = confy::load_path::<FbxProfile>(&file) {
+                    config.profiles.insert(config.current.clone(), legacy);
+                }
+            }
+        }
+        config.path = path.map(Path::to_path_buf);
+        config
     }
 
     pub fn save(&self) -> Result<(), confy::ConfyError> {
-        confy::store(APP_NAME, CONFIG_NAME, self)
+        confy::store_path(Self::file(self.path.as_deref()), self)
+    }
+
+    /// Path of the configuration file, whether it exists yet or not.
+    pub fn location(&self) -> PathBuf {
+        Self::file(self.path.as_deref())
+    }
+
+    fn file(path: Option<&Path>) -> PathBuf {
+        path.map(Path::to_path_buf).unwrap_or_else(|| {
+            confy::get_configuration_file_path(APP_NAME, CONFIG_NAME)
+                .unwrap_or_else(|_| PathBuf::from(format!("{}.toml", CONFIG_NAME)))
+        })
+    }
+
+    pub fn profile(&self, name: &str) -> Option<&FbxProfile> {
+        self.profiles.get(name)
+    }
+
+    pub fn secret_store(&self, backend: SecretBackend) -> Box<dyn SecretStore> {
+        secret_store(self.path.as_deref(), self.passphrase.as_deref(), backend)
     }
 
-    pub fn reset(&self) -> Result<(), confy::ConfyError> {
-        println!("Resetting configuration: {:?}", FbxConfig::default());
-        confy::store(APP_NAME, CONFIG_NAME, FbxConfig::default())
+    /// Store holding the app token of `app`.
+    pub fn backend(&self, app: &FbxApp) -> SecretBackend {
+        app.secrets.unwrap_or(self.secrets)
+    }
+
+    pub fn app_token(&self, profile: &str, app: &FbxApp) -> Result<Option<String>, SecretError> {
+        match &app.status {
+            FbxAppStatus::Pending => Ok(None),
+            FbxAppStatus::Granted(app_token) => Ok(Some(app_token.clone())),
+            FbxAppStatus::Stored => self.load_app_token(profile, app),
+        }
+    }
+
+    /// Reads the app token of `app` kept in the secret store, pending or
+    /// granted.
+    pub fn load_app_token(
+        &self,
+        profile: &str,
+        app: &FbxApp,
+    ) -> Result<Option<String>, SecretError> {
+        self.secret_store(self.backend(app))
+            .get(&self.app_token_key(profile))
+    }
+
+    /// Stores an app token right away, e.g. while its authorization is
+    /// pending, with the same fallback as [`FbxConfig::store_secrets`]. Gives
+    /// the store it ended up in, to be recorded on the app.
+    pub fn store_app_token(
+        &self,
+        profile: &str,
+        app_token: &str,
+    ) -> Result<SecretBackend, SecretError> {
+        let stores = |backend| self.secret_store(backend);
+        store_with_fallback(
+            &stores,
+            self.secrets,
+            &self.app_token_key(profile),
+            app_token,
+        )
+    }
+
+    /// Moves every granted app token to the preferred secret store. A profile
+    /// whose token can't reach the keyring falls back to the encrypted file,
+    /// which is recorded on its app, the others keep their store. Tokens of
+    /// configs written before the secret store existed are migrated the same
+    /// way.
+    pub fn store_secrets(&mut self) -> Result<(), SecretError> {
+        let (path, passphrase) = (self.path.clone(), self.passphrase.clone());
+        self.store_secrets_in(&|backend| {
+            secret_store(path.as_deref(), passphrase.as_deref(), backend)
+        })
+    }
+
+    fn store_secrets_in(
+        &mut self,
+        stores: &dyn Fn(SecretBackend) -> Box<dyn SecretStore>,
+    ) -> Result<(), SecretError> {
+        for (name, profile) in self.profiles.iter_mut() {
+            let Some(app) = profile.app.as_mut() else {
+                continue;
+            };
+            if let FbxAppStatus::Granted(app_token) = &app.status {
+                let key = app_token_key(self.path.as_deref(), name);
+                app.secrets = Some(store_with_fallback(stores, self.secrets, &key, app_token)?);
+                app.status = FbxAppStatus::Stored;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn forget_app_token(&self, profile: &str, app: &FbxApp) -> Result<(), SecretError> {
+        self.secret_store(self.backend(app))
+            .delete(&self.app_token_key(profile))
+    }
+
+    fn app_token_key(&self, profile: &str) -> String {
+        app_token_key(self.path.as_deref(), profile)
+    }
+}
+
+/// Store of `backend`, the encrypted file lives next to the configuration
+/// file at `path`.
+fn secret_store(
+    path: Option<&Path>,
+    passphrase: Option<&str>,
+    backend: SecretBackend,
+) -> Box<dyn SecretStore> {
+    match backend {
+        SecretBackend::Keyring => Box::new(KeyringStore),
+        SecretBackend::EncryptedFile => {
+            let store = EncryptedFileStore::new(
+                FbxConfig::file(path).with_file_name(format!("{}.toml", SECRETS_NAME)),
+            );
+            if let Some(passphrase) = passphrase {
+                let _ = store.passphrase.set(passphrase.to_string());
+            }
+            Box::new(store)
+        }
+    }
+}
+
+/// Stores a secret in the `preferred` store, or in the encrypted file when
+/// the keyring is unavailable, and gives the store used.
+fn store_with_fallback(
+    stores: &dyn Fn(SecretBackend) -> Box<dyn SecretStore>,
+    preferred: SecretBackend,
+    key: &str,
+    secret: &str,
+) -> Result<SecretBackend, SecretError> {
+    match stores(preferred).set(key, secret) {
+        Err(SecretError::Unavailable(_)) if preferred == SecretBackend::Keyring => {
+            let fallback = SecretBackend::EncryptedFile;
+            stores(fallback).set(key, secret).map(|_| fallback)
+        }
+        result => result.map(|_| preferred),
+    }
+}
+
+/// Reads an environment variable, empty values count as unset.
+pub fn env_var(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+/// Key of the app token of `profile` in the secret store. The keyring is
+/// shared by every configuration file, so the key of a profile that does not
+/// come from the default file also holds the path of its file.
+fn app_token_key(path: Option<&Path>, profile: &str) -> String {
+    match path {
+        Some(path) => format!(
+            "app_token.{}@{}",
+            profile,
+            std::path::absolute(path)
+                .unwrap_or(path.to_path_buf())
+                .display()
+        ),
+        None => format!("app_token.{}", profile),
+    }
+}
+
+impl FbxProfile {
+    /// Overrides the profile with `FBX_URL`, `FBX_API_VERSION` and
+    /// `FBX_APP_TOKEN` (registered as `FBX_APP_ID`), returns whether any of
+    /// them is set.
+    pub fn apply_env(&mut self) -> bool {
+        let mut overridden = false;
+        if let Some(url) = env_var(URL_ENV) {
+            self.pref.set_base_url(url);
+            self.pref.remote = false;
+            self.session = None;
+            overridden = true;
+        }
+        if let Some(variable) = env_var(API_VERSION_ENV) {
+            match FreeboxMajorVersion::from_api_version(variable.trim_start_matches('v')) {
+                Some(version) => {
+                    self.pref.version = version;
+                    self.pref
+                        .api_base_url
+                        .get_or_insert(DEFAULT_API_BASE_URL.to_string());
+                    overridden = true;
+                }
+                // Left to the detection.
+                None => eprintln!("{}", Message::ErrorInvalidApiVersion(&variable)),
+            }
+        }
+        if let Some(app_token) = env_var(APP_TOKEN_ENV) {
+            let app = self.app.get_or_insert_with(|| FbxApp {
+                created_at: Utc::now(),
+                ..FbxApp::default()
+            });
+            app.status = FbxAppStatus::Granted(app_token);
+            if let Some(app_id) = env_var(APP_ID_ENV) {
+                app.app_id = app_id;
+            }
+            self.session = None;
+            overridden = true;
+        }
+        overridden
+    }
+
+    pub fn reset(&mut self) {
+        info!("{}", Message::ConfigResetting);
+        *self = FbxProfile::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Plain file stand-in for the keyring.
+    struct FileStore(PathBuf);
+
+    impl SecretStore for FileStore {
+        fn get(&self, key: &str) -> Result<Option<String>, SecretError> {
+            let secrets: BTreeMap<String, String> = confy::load_path(&self.0).unwrap();
+            Ok(secrets.get(key).cloned())
+        }
+
+        fn set(&self, key: &str, secret: &str) -> Result<(), SecretError> {
+            let mut secrets: BTreeMap<String, String> = confy::load_path(&self.0).unwrap();
+            secrets.insert(key.to_string(), secret.to_string());
+            confy::store_path(&self.0, secrets).map_err(|e| SecretError::Unavailable(e.to_string()))
+        }
+
+        fn delete(&self, key: &str) -> Result<(), SecretError> {
+            let mut secrets: BTreeMap<String, String> = confy::load_path(&self.0).unwrap();
+            secrets.remove(key);
+            confy::store_path(&self.0, secrets).map_err(|e| SecretError::Unavailable(e.to_string()))
+        }
+    }
+
+    /// Keyring of a session without D-Bus.
+    struct UnavailableStore;
+
+    impl SecretStore for UnavailableStore {
+        fn get(&self, _: &str) -> Result<Option<String>, SecretError> {
+            Err(SecretError::Unavailable("no keyring".to_string()))
+        }
+
+        fn set(&self, _: &str, _: &str) -> Result<(), SecretError> {
+            Err(SecretError::Unavailable("no keyring".to_string()))
+        }
+
+        fn delete(&self, _: &str) -> Result<(), SecretError> {
+            Err(SecretError::Unavailable("no keyring".to_string()))
+        }
+    }
+
+    fn granted_profile(app_token: &str) -> FbxProfile {
+        FbxProfile {
+            app: Some(FbxApp {
+                status: FbxAppStatus::Granted(app_token.to_string()),
+                ..FbxApp::default()
+            }),
+            ..FbxProfile::default()
+        }
+    }
+
+    #[test]
+    fn granted_tokens_are_moved_to_the_store() {
+        let directory = TempDir::new().unwrap();
+        let store = FileStore(directory.path().join("store.toml"));
+        let mut config = FbxConfig::default();
+        config
+            .profiles
+            .insert("home".to_string(), granted_profile("home-token"));
+        config
+            .profiles
+            .insert("parents".to_string(), FbxProfile::default());
+
+        config
+            .store_secrets_in(&|_| Box::new(FileStore(store.0.clone())))
+            .unwrap();
+
+        let app = config.profiles["home"].app.as_ref().unwrap();
+        assert!(matches!(app.status, FbxAppStatus::Stored));
+        assert_eq!(app.secrets, Some(SecretBackend::Keyring));
+        assert_eq!(
+            store.get("app_token.home").unwrap(),
+            Some("home-token".to_string())
+        );
+        assert_eq!(store.get("app_token.parents").unwrap(), None);
+    }
+
+    #[test]
+    fn app_token_keys_depend_on_the_config_file() {
+        let default = FbxConfig::default();
+        let work = FbxConfig {
+            path: Some(PathBuf::from("/etc/fbx/work.toml")),
+            ..FbxConfig::default()
+        };
+        let home = FbxConfig {
+            path: Some(PathBuf::from("/etc/fbx/home.toml")),
+            ..FbxConfig::default()
+        };
+
+        assert_eq!(default.app_token_key("home"), "app_token.home");
+        assert_eq!(
+            work.app_token_key("home"),
+            "app_token.home@/etc/fbx/work.toml"
+        );
+        assert_ne!(work.app_token_key("home"), home.app_token_key("home"));
+    }
+
+    #[test]
+    fn granted_tokens_are_never_serialized() {
+        let mut config = FbxConfig::default();
+        config
+            .profiles
+            .insert("home".to_string(), granted_profile("home-token"));
+
+        assert!(serde_json::to_string(&config).is_err());
+    }
+
+    #[test]
+    fn encrypted_file_needs_the_same_passphrase() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("encrypted.toml");
+        let store = EncryptedFileStore::new(path.clone());
+        store.passphrase.set("hunter2".to_string()).unwrap();
+        store.set("app_token.home", "home-token").unwrap();
+        assert!(!std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("home-token"));
+        assert_eq!(
+            store.get("app_token.home").unwrap(),
+            Some("home-token".to_string())
+        );
+
+        let wrong = EncryptedFileStore::new(path);
+        wrong.passphrase.set("wrong".to_string()).unwrap();
+        assert_eq!(
+            wrong.get("app_token.home"),
+            Err(SecretError::InvalidPassphrase)
+        );
+        assert_eq!(
+            wrong.set("app_token.parents", "parents-token"),
+            Err(SecretError::InvalidPassphrase)
+        );
+    }
+
+    #[test]
+    fn unavailable_keyring_only_moves_the_new_token() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("config.toml");
+        let file = directory.path().join("secrets.toml");
+        let mut config = FbxConfig::load(Some(&path));
+        let mut home = granted_profile("home-token");
+        home.app.as_mut().unwrap().status = FbxAppStatus::Stored;
+        config.profiles.insert("home".to_string(), home);
+        config
+            .profiles
+            .insert("parents".to_string(), granted_profile("parents-token"));
+
+        config
+            .store_secrets_in(&|backend| match backend {
+                SecretBackend::Keyring => Box::new(UnavailableStore),
+                SecretBackend::EncryptedFile => Box::new(FileStore(file.clone())),
+            })
+            .unwrap();
+        config.save().unwrap();
+
+        let config = FbxConfig::load(Some(&path));
+        assert_eq!(config.secrets, SecretBackend::Keyring);
+        let home = config.profiles["home"].app.as_ref().unwrap();
+        assert_eq!(config.backend(home), SecretBackend::Keyring);
+        let parents = config.profiles["parents"].app.as_ref().unwrap();
+        assert!(matches!(parents.status, FbxAppStatus::Stored));
+        assert_eq!(config.backend(parents), SecretBackend::EncryptedFile);
+        let key = config.app_token_key("parents");
+        assert_eq!(
+            FileStore(file).get(&key).unwrap(),
+            Some("parents-token".to_string())
+        );
+    }
+
+    #[test]
+    fn legacy_granted_tokens_are_migrated() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("config.toml");
+        let store = directory.path().join("store.toml");
+        std::fs::write(
+            &path,
+            r#"
+[app]
+app_id = "fr.freebox.fbxcli"
+version = "1.0.0"
+track_id = 42
+status = { Granted = "legacy-token" }
+created_at = "2024-01-01T00:00:00Z"
+
+[session]
+token_session = "session-1"
+created_at = "2024-01-01T00:00:00Z"
+
+[pref]
+base_url = "http://192.168.1.254"
+version = "V8"
+"#,
+        )
+        .unwrap();
+
+        let mut config = FbxConfig::load(Some(&path));
+        let app = config.profiles[DEFAULT_PROFILE].app.as_ref().unwrap();
+        assert!(matches!(&app.status, FbxAppStatus::Granted(token) if token == "legacy-token"));
+        assert_eq!(
+            config.profiles[DEFAULT_PROFILE].pref.base_url,
+            "http://192.168.1.254"
+        );
+        config
+            .store_secrets_in(&|_| Box::new(FileStore(store.clone())))
+            .unwrap();
+        config.save().unwrap();
+
+        assert!(!std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("legacy-token"));
+        let config = FbxConfig::load(Some(&path));
+        let app = config.profiles[DEFAULT_PROFILE].app.as_ref().unwrap();
+        assert!(matches!(app.status, FbxAppStatus::Stored));
+        let key = config.app_token_key(DEFAULT_PROFILE);
+        assert_eq!(
+            FileStore(store).get(&key).unwrap(),
+            Some("legacy-token".to_string())
+        );
     }
 }
@@ -0,0 +1,389 @@
+//! Fake Freebox for the tests: a local HTTP server answering like the
+//! Freebox OS API, with the login challenge, the HMAC session, the app
+//! authorization tracking and canned answers of `tests/fixtures`.
+use crate::app::App;
+use crate::config::{FbxApp, FbxAppStatus, FbxConfig, SecretBackend, DEFAULT_PROFILE};
+use crate::models::args::Cli;
+use clap::Parser;
+use hmac::{Hmac, Mac};
+use percent_encoding::percent_decode_str;
+use serde_json::{json, Value};
+use sha1::Sha1;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tempfile::TempDir;
+
+pub const APP_ID: &str = "fr.freebox.fbxcli.tests";
+pub const APP_TOKEN: &str = "dyNYgfK0Ya6FWGqq83sBHa7TwzWo+pg4fDFUJHShcjVYzTfaRrZzm93p7OTAfH/0";
+pub const TRACK_ID: i32 = 42;
+const API_PATH: &str = "/api/v8";
+const PASSPHRASE: &str = "tests";
+
+/// Path of `/api_version`, the only one served at the root of the box.
+const API_VERSION_PATH: &str = "/api_version/";
+
+/// Fixture answered for each method and path under the api path, but for
+/// `/api_version`.
+const FIXTURES: [(&str, &str, &str); 30] = [
+    ("GET", API_VERSION_PATH, "api_version"),
+    ("GET", "/lcd/config/", "lcd_config"),
+    ("PUT", "/lcd/config/", "lcd_config"),
+    ("GET", "/airmedia/config/", "airmedia_config"),
+    ("PUT", "/airmedia/config/", "airmedia_config"),
+    ("GET", "/airmedia/receivers/", "airmedia_receivers"),
+    ("POST", "/airmedia/receivers/Freebox Player/", "success"),
+    ("POST", "/airmedia/receivers/Salon/", "success"),
+    ("GET", "/lang/", "lang"),
+    ("POST", "/lang/", "success"),
+    ("GET", "/notif/targets/", "notif_targets"),
+    ("POST", "/notif/targets/", "notif_target"),
+    ("PUT", "/notif/targets/c5e4a9d1/", "notif_target"),
+    ("DELETE", "/notif/targets/c5e4a9d1/", "success"),
+    ("DELETE", "/notif/targets/0b7f22e3/", "success"),
+    ("GET", "/connection/config/", "connection_config"),
+    ("PUT", "/connection/config/", "connection_config"),
+    ("GET", "/lan/browser/pub/", "lan_browser_pub"),
+    ("GET", "/dhcp/dynamic_lease/", "dhcp_dynamic_lease"),
+    ("GET", "/dhcp/static_lease/", "dhcp_static_lease"),
+    ("GET", "/fw/redir/", "fw_redir"),
+    ("GET", "/vm/", "vm"),
+    ("GET", "/system/", "system"),
+    ("POST", "/system/reboot/", "success"),
+    ("POST", "/system/shutdown/", "success"),
+    ("GET", "/wifi/config/", "wifi_config"),
+    ("PUT", "/wifi/config/", "wifi_config"),
+    ("GET", "/wifi/ap/", "wifi_ap"),
+    ("GET", "/wifi/bss/", "wifi_bss"),
+    ("PUT", "/wifi/bss/F4:CA:E5:00:00:02/", "wifi_bss_main"),
+];
+
+/// Request received by the box, the path is the one under the api path, or
+/// the full one for requests outside of it.
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    /// Sent outside of the api path, e.g. to `/api_version`.
+    pub root: bool,
+    pub session_token: Option<String>,
+    pub body: Value,
+}
+
+pub struct FakeFreebox {
+    pub url: String,
+    state: Arc<Mutex<State>>,
+    /// Configuration directories of the apps, removed with the box.
+    directories: Mutex<Vec<TempDir>>,
+}
+
+struct State {
+    answers: HashMap<(String, String), (u16, Value)>,
+    requests: Vec<Request>,
+    challenge: String,
+    sessions: Vec<String>,
+    permissions: Value,
+    /// Status of the authorization for each poll, the last one is repeated.
+    authorization: Vec<&'static str>,
+}
+
+impl FakeFreebox {
+    /// Starts a box on a free local port, it lives until the tests end.
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("no local port available");
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let answers = FIXTURES
+            .iter()
+            .map(|(method, path, fixture)| {
+                let key = (method.to_string(), path.to_string());
+                (key, (200, Self::fixture(fixture)))
+            })
+            .collect();
+        let state = Arc::new(Mutex::new(State {
+            answers,
+            requests: vec![],
+            challenge: "Bj6xMqoe+DCHD44KqBljJ579seOXNWr2".to_string(),
+            sessions: vec![],
+            permissions: json!({
+                "settings": true,
+                "contacts": false,
+                "calls": false,
+                "explorer": true,
+                "downloader": true,
+                "parental": true,
+                "pvr": true,
+                "profile": false,
+                "camera": false,
+                "home": false,
+                "player": true,
+                "tv": true,
+                "vm": true,
+            }),
+            authorization: vec!["granted"],
+        }));
+        let served = state.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let _ = Self::serve(stream, &served);
+            }
+        });
+        FakeFreebox {
+            url,
+            state,
+            directories: Mutex::new(vec![]),
+        }
+    }
+
+    /// App running `args` (the command line without `fbx`) quietly against
+    /// the box, with a granted app and no session yet. Its configuration is
+    /// written in a directory of its own, and nothing is read from the
+    /// environment.
+    pub async fn app(&self, args: &[&str]) -> App {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("config.toml");
+        let mut command_line = vec!["fbx", "-q", "--config", path.to_str().unwrap()];
+        // Given explicitly, or clap would read them from the environment.
+        for (name, value) in [
+            ("--profile", DEFAULT_PROFILE),
+            ("--timeout", "5"),
+            ("--retries", "2"),
+        ] {
+            if !args.contains(&name) {
+                command_line.extend([name, value]);
+            }
+        }
+        let cli = Cli::parse_from(command_line.iter().chain(args));
+        let mut profiles = FbxConfig::load(Some(&path));
+        profiles.secrets = SecretBackend::EncryptedFile;
+        profiles.passphrase = Some(PASSPHRASE.to_string());
+        self.directories.lock().unwrap().push(directory);
+        let mut app = App::without_env(cli, profiles);
+        app.config.pref.set_base_url(self.url.clone());
+        app.config.app = Some(FbxApp {
+            app_id: APP_ID.to_string(),
+            status: FbxAppStatus::Granted(APP_TOKEN.to_string()),
+            ..FbxApp::default()
+        });
+        app.initialize().await.unwrap();
+        app
+    }
+
+    /// Replaces the answer to `method` on `path`, e.g. with an error.
+    pub fn answer(&self, method: &str, path: &str, status: u16, answer: Value) {
+        self.state()
+            .answers
+            .insert((method.to_string(), path.to_string()), (status, answer));
+    }
+
+    /// Opens the next sessions without `permission`.
+    pub fn deny(&self, permission: &str) {
+        self.state().permissions[permission] = json!(false);
+    }
+
+    /// Status of the authorization for each poll, e.g. `["pending", "granted"]`.
+    pub fn authorization(&self, statuses: Vec<&'static str>) {
+        self.state().authorization = statuses;
+    }
+
+    /// Requests received so far for `method` on `path`.
+    pub fn requests(&self, method: &str, path: &str) -> Vec<Request> {
+        self.state()
+            .requests
+            .iter()
+            .filter(|request| request.method == method && request.path == path)
+            .cloned()
+            .collect()
+    }
+
+    /// Tokens of the sessions still open.
+    pub fn sessions(&self) -> Vec<String> {
+        self.state().sessions.clone()
+    }
+
+    /// Content of `tests/fixtures/<name>.json`.
+    pub fn fixture(name: &str) -> Value {
+        let path = fixture_path(&format!("{}.json", name));
+        let fixture = std::fs::read_to_string(&path).expect("missing fixture");
+        serde_json::from_str(&fixture).expect("invalid fixture")
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap()
+    }
+
+    fn serve(mut stream: TcpStream, state: &Mutex<State>) -> std::io::Result<()> {
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let mut request_line = line.split_whitespace();
+        let method = request_line.next().unwrap_or_default().to_string();
+        let target = request_line.next().unwrap_or_default().to_string();
+        let mut headers = HashMap::new();
+        loop {
+            line.clear();
+            reader.read_line(&mut line)?;
+            match line.trim_end().split_once(':') {
+                Some((name, value)) => {
+                    headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string())
+                }
+                None => break,
+            };
+        }
+        let length = headers
+            .get("content-length")
+            .and_then(|length| length.parse().ok())
+            .unwrap_or(0);
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+
+        let path = target.split('?').next().unwrap_or_default();
+        let path = format!(
+            "{}/",
+            percent_decode_str(path)
+                .decode_utf8_lossy()
+                .trim_end_matches('/')
+        );
+        let request = Request {
+            method,
+            path: path.strip_prefix(API_PATH).unwrap_or(&path).to_string(),
+            root: !path.starts_with(&format!("{}/", API_PATH)),
+            session_token: headers.get("x-fbx-app-auth").cloned(),
+            body: serde_json::from_slice(&body).unwrap_or_default(),
+        };
+        let (status, answer) = state.lock().unwrap().answer(request);
+        let answer = answer.to_string();
+        write!(
+            stream,
+            "HTTP/1.1 {} Fake\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            answer.len(),
+            answer
+        )?;
+        stream.flush()
+    }
+}
+
+impl State {
+    fn answer(&mut self, request: Request) -> (u16, Value) {
+        self.requests.push(request.clone());
+        let logged_in = request
+            .session_token
+            .as_ref()
+            .is_some_and(|token| self.sessions.contains(token));
+        if request.root != (request.path == API_VERSION_PATH) {
+            return error(404, "invalid_request", "Requête invalide");
+        }
+        let track = format!("/login/authorize/{}/", TRACK_ID);
+        match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/login/") => (
+                200,
+                json!({
+                    "success": true,
+                    "result": {
+                        "logged_in": logged_in,
+                        "challenge": self.challenge,
+                        "password_salt": "PJ8cEpKbjm",
+                        "password_set": true,
+                    }
+                }),
+            ),
+            ("POST", "/login/authorize/") => (
+                200,
+                json!({
+                    "success": true,
+                    "result": {"app_token": APP_TOKEN, "track_id": TRACK_ID}
+                }),
+            ),
+            ("GET", path) if path == track => {
+                let status = match self.authorization.len() {
+                    0 | 1 => self.authorization.first().copied().unwrap_or("unknown"),
+                    _ => self.authorization.remove(0),
+                };
+                (
+                    200,
+                    json!({
+                        "success": true,
+                        "result": {"status": status, "challenge": self.challenge}
+                    }),
+                )
+            }
+            ("POST", "/login/session/") => {
+                if request.body["password"] != password(APP_TOKEN, &self.challenge) {
+                    return error(
+                        403,
+                        "invalid_token",
+                        "Erreur d'authentification de l'application",
+                    );
+                }
+                let session_token = format!("session-{}", self.sessions.len() + 1);
+                self.sessions.push(session_token.clone());
+                self.challenge = format!("{}+", self.challenge);
+                (
+                    200,
+                    json!({
+                        "success": true,
+                        "result": {
+                            "session_token": session_token,
+                            "challenge": self.challenge,
+                            "permissions": self.permissions,
+                        }
+                    }),
+                )
+            }
+            ("POST", "/login/logout/") => {
+                if !logged_in {
+                    return error(
+                        403,
+                        "auth_required",
+                        "Vous devez vous connecter pour accéder à cette fonction",
+                    );
+                }
+                self.sessions
+                    .retain(|token| Some(token) != request.session_token.as_ref());
+                (200, json!({"success": true}))
+            }
+            (method, path) => {
+                if !logged_in && !request.root {
+                    return error(
+                        403,
+                        "auth_required",
+                        "Vous devez vous connecter pour accéder à cette fonction",
+                    );
+                }
+                self.answers
+                    .get(&(method.to_string(), path.to_string()))
+                    .cloned()
+                    .unwrap_or_else(|| error(404, "invalid_request", "Requête invalide"))
+            }
+        }
+    }
+}
+
+/// Path of `tests/fixtures/<file>`.
+pub fn fixture_path(file: &str) -> String {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(file)
+        .display()
+        .to_string()
+}
+
+fn error(status: u16, error_code: &str, msg: &str) -> (u16, Value) {
+    (
+        status,
+        json!({"success": false, "error_code": error_code, "msg": msg}),
+    )
+}
+
+/// HMAC-SHA1 of the challenge keyed by the app token, in hexadecimal.
+fn password(app_token: &str, challenge: &str) -> String {
+    let mut hmac: Hmac<Sha1> = Mac::new_from_slice(app_token.as_bytes()).unwrap();
+    hmac.update(challenge.as_bytes());
+    hmac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
@@ -0,0 +1,267 @@
+use crate::app::App;
+use crate::handlers::new_table;
+use crate::i18n::Message;
+use crate::info;
+use crate::models::args::{AirMediaConfigSetArgs, AirMediaPlayArgs, AirMediaStopArgs, ListArgs};
+use crate::models::exception::ClientError;
+use crate::models::freebox::air_media::{
+    AirMediaAction, AirMediaConfigBody, AirMediaReceiver, AirMediaReceiverRequest,
+};
+use crate::models::freebox::authorization::Permission;
+use crate::output;
+use crate::services::api::FreeboxOSApiCalls;
+use comfy_table::Cell;
+use inquire::Password;
+
+pub struct AirMedia;
+
+impl AirMedia {
+    pub async fn get_config(app: &mut App) -> Result<(), ClientError> {
+        let session_token = app.session_token().await?;
+        let config = app
+            .api
+            .air_media_config(&app.client, session_token)
+            .await?
+            .result;
+        output::item(app.cli.output, &config, |config| {
+            println!("{}", Message::AirMediaEnabled(config.enabled))
+        })
+    }
+
+    pub async fn set_config(
+        app: &mut App,
+        args: &AirMediaConfigSetArgs,
+    ) -> Result<(), ClientError> {
+        if args.enabled.is_none() && args.password.is_none() {
+            return Err(ClientError::InvalidArgument(
+                Message::ErrorNothingToUpdate("--enabled, --password").to_string(),
+            ));
+        }
+        let session_token = app.session_token_for(Permission::Settings).await?;
+        let body = AirMediaConfigBody {
+            enabled: args.enabled,
+            password: args.password.clone(),
+        };
+        let config = app
+            .api
+            .update_air_media_config(&app.client, session_token, body)
+            .await?
+            .result;
+        output::item(app.cli.output, &config, |config| {
+            info!("{}", Message::AirMediaConfigUpdated);
+            println!("{}", Message::AirMediaEnabled(config.enabled))
+        })
+    }
+
+    pub async fn receivers(app: &mut App, args: &ListArgs) -> Result<(), ClientError> {
+        let session_token = app.session_token().await?;
+        let receivers = app
+            .api
+            .air_media_receivers(&app.client, session_token)
+            .await?
+            .result;
+        output::list(app.cli.output, args, &receivers, |receivers| {
+            let mut table = new_table(vec![
+                Message::AirMediaName.to_string(),
+                Message::AirMediaPassword.to_string(),
+                Message::AirMediaPhoto.to_string(),
+                Message::AirMediaAudio.to_string(),
+                Message::AirMediaVideo.to_string(),
+                Message::AirMediaScreen.to_string(),
+            ]);
+            for receiver in receivers {
+                table.add_row(vec![
+                    Cell::new(&receiver.name),
+                    Cell::new(receiver.password_protected),
+                    Cell::new(receiver.capabilities.photo),
+                    Cell::new(receiver.capabilities.audio),
+                    Cell::new(receiver.capabilities.video),
+                    Cell::new(receiver.capabilities.screen),
+                ]);
+            }
+            table
+        })
+    }
+
+    pub async fn play(app: &mut App, args: &AirMediaPlayArgs) -> Result<(), ClientError> {
+        let session_token = app.session_token_for(Permission::Player).await?;
+        let receiver = Self::find_receiver(app, session_token.clone(), &args.receiver).await?;
+        if !receiver.capabilities.supports(&args.media_type) {
+            return Err(ClientError::InvalidArgument(
+                Message::ErrorAirMediaUnsupportedMedia.to_string(),
+            ));
+        }
+        let body = AirMediaReceiverRequest {
+            action: AirMediaAction::Start,
+            media_type: args.media_type.clone(),
+            media: Some(args.url.clone()),
+            position: args.position,
+            password: Self::receiver_password(&receiver, args.password.clone())?,
+        };
+        app.api
+            .air_media_receiver_request(&app.client, session_token, &receiver.name, body)
+            .await?;
+        info!("{}", Message::AirMediaPlaying(&receiver.name));
+        Ok(())
+    }
+
+    pub async fn stop(app: &mut App, args: &AirMediaStopArgs) -> Result<(), ClientError> {
+        let session_token = app.session_token_for(Permission::Player).await?;
+        let receiver = Self::find_receiver(app, session_token.clone(), &args.receiver).await?;
+        let body = AirMediaReceiverRequest {
+            action: AirMediaAction::Stop,
+            media_type: args.media_type.clone(),
+            media: None,
+            position: None,
+            password: Self::receiver_password(&receiver, args.password.clone())?,
+        };
+        app.api
+            .air_media_receiver_request(&app.client, session_token, &receiver.name, body)
+            .await?;
+        info!("{}", Message::AirMediaStopped(&receiver.name));
+        Ok(())
+    }
+
+    async fn find_receiver(
+        app: &mut App,
+        session_token: String,
+        name: &str,
+    ) -> Result<AirMediaReceiver, ClientError> {
+        app.api
+            .air_media_receivers(&app.client, session_token)
+            .await?
+            .result
+            .into_iter()
+            .find(|receiver| receiver.name.eq_ignore_ascii_case(name))
+            .ok_or(ClientError::NotFound(
+                Message::ErrorAirMediaReceiverNotFound(name).to_string(),
+            ))
+    }
+
+    /// Asks for the receiver password when it is protected and none was given
+    /// on the command line.
+    fn receiver_password(
+        receiver: &AirMediaReceiver,
+        password: Option<String>,
+    ) -> Result<Option<String>, ClientError> {
+        if !receiver.password_protected || password.is_some() {
+            return Ok(password);
+        }
+        Password::new(&Message::AirMediaPasswordPrompt(&receiver.name).to_string())
+            .without_confirmation()
+            .prompt()
+            .map(Some)
+            .map_err(|_| {
+                ClientError::InvalidArgument(Message::ErrorAirMediaPasswordRequired.to_string())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fake::FakeFreebox;
+    use crate::models::exception::ClientError;
+    use crate::output::captured;
+    use crate::run;
+    use serde_json::{json, Value};
+
+    #[tokio::test]
+    async fn config_set_sends_only_the_given_fields() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx
+            .app(&[
+                "--output",
+                "json",
+                "airmedia",
+                "config",
+                "set",
+                "--enabled",
+                "false",
+            ])
+            .await;
+
+        run(&mut app).await.unwrap();
+
+        let requests = fbx.requests("PUT", "/airmedia/config/");
+        assert_eq!(requests[0].body, json!({"enabled": false}));
+        let config: Value = serde_json::from_str(&captured()).unwrap();
+        assert!(config.get("enabled").is_some());
+    }
+
+    #[tokio::test]
+    async fn play_starts_the_media_on_the_receiver() {
+        let fbx = FakeFreebox::start();
+        let url = "http://example.com/video.mp4";
+        let mut app = fbx.app(&["airmedia", "play", "freebox player", url]).await;
+
+        run(&mut app).await.unwrap();
+
+        let requests = fbx.requests("POST", "/airmedia/receivers/Freebox Player/");
+        assert_eq!(
+            requests[0].body,
+            json!({"action": "start", "media_type": "video", "media": url})
+        );
+    }
+
+    #[tokio::test]
+    async fn play_checks_the_receiver_capabilities() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx
+            .app(&["airmedia", "play", "Salon", "http://example.com/video.mp4"])
+            .await;
+
+        let result = run(&mut app).await;
+
+        assert!(matches!(result, Err(ClientError::InvalidArgument(_))));
+        assert!(fbx
+            .requests("POST", "/airmedia/receivers/Salon/")
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn stop_sends_the_receiver_password() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx
+            .app(&["airmedia", "stop", "Salon", "-t", "audio", "-p", "1234"])
+            .await;
+
+        run(&mut app).await.unwrap();
+
+        let requests = fbx.requests("POST", "/airmedia/receivers/Salon/");
+        assert_eq!(
+            requests[0].body,
+            json!({"action": "stop", "media_type": "audio", "password": "1234"})
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_receiver_is_not_found() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["airmedia", "stop", "Cuisine"]).await;
+
+        let result = run(&mut app).await;
+
+        assert!(matches!(result, Err(ClientError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn play_and_stop_need_the_player_permission() {
+        let fbx = FakeFreebox::start();
+        fbx.deny("player");
+        let url = "http://example.com/video.mp4";
+        let commands: [&[&str]; 2] = [
+            &["airmedia", "play", "Freebox Player", url],
+            &["airmedia", "stop", "Freebox Player", "-t", "video"],
+        ];
+        for args in commands {
+            let mut app = fbx.app(args).await;
+
+            let result = run(&mut app).await;
+
+            assert!(matches!(result, Err(ClientError::PermissionDenied(_))));
+        }
+        assert!(fbx
+            .requests("POST", "/airmedia/receivers/Freebox Player/")
+            .is_empty());
+    }
+}
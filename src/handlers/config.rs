@@ -1,66 +1,574 @@
-use crate::app::App;
+use crate::app::{major_version, root_certificates, App};
+use crate::client::parse_root_certificates;
+use crate::config::{FbxAppStatus, FbxPreferences, FbxProfile, SecretBackend};
+use crate::handlers::new_table;
+use crate::i18n::{self, Locale, Message};
+use crate::info;
+use crate::models::args::{
+    ConfigHttpsEnableArgs, ConfigProfileAddArgs, ConfigProfileRmArgs, ListArgs,
+};
 use crate::models::exception::ClientError;
-use comfy_table::presets::UTF8_FULL;
-use comfy_table::{Attribute, Cell, Color, ContentArrangement, Table};
+use crate::output;
+use crate::services::api::FreeboxOSApiCalls;
+use comfy_table::Cell;
 use inquire::Confirm;
+use serde::Serialize;
 use url::Url;
 
 pub struct Config;
 
 impl Config {
     pub async fn change_url(app: &mut App, url: String) -> Result<(), ClientError> {
-        match Url::parse(&url) {
-            Ok(url) => {
-                println!("Url: {:?}", url);
-                app.config.pref.base_url = url.to_string();
-                println!("Url updated !");
-            }
-            Err(_) => {
-                println!("Invalid url provided !");
+        let url = Url::parse(&url)
+            .map_err(|_| ClientError::InvalidUrl(Message::ConfigInvalidUrl.to_string()))?;
+        info!("{}", Message::ConfigUrl(url.as_str()));
+        Self::switch_url(app, &url)?;
+        info!("{}", Message::ConfigUrlUpdated);
+        Ok(())
+    }
+
+    /// Points the profile at `url`. The session and the app token are only
+    /// valid on the box which issued them, they are forgotten when `url` is
+    /// on another host than the current or the recorded LAN address.
+    pub fn switch_url(app: &mut App, url: &Url) -> Result<(), ClientError> {
+        let pref = &app.config.pref;
+        let same_box = [Some(&pref.base_url), pref.http_url.as_ref()]
+            .into_iter()
+            .flatten()
+            .filter_map(|known| Url::parse(known).ok())
+            .any(|known| known.host_str() == url.host_str());
+        if !same_box {
+            if let Some(fbx_app) = &app.config.app {
+                app.profiles.forget_app_token(&app.profile, fbx_app)?;
+                app.config.app = None;
             }
+            app.config.session = None;
+            let pref = &mut app.config.pref;
+            pref.http_url = None;
+            pref.remote = false;
+            pref.remote_url = None;
+            info!("{}", Message::ConfigBoxChanged);
         }
+        app.config.pref.set_base_url(url.to_string());
         Ok(())
     }
 
     pub async fn reset(app: &mut App, confirm: bool) -> Result<(), ClientError> {
         if !confirm {
-            Confirm::new("Êtes-vous sûr de vouloir réinitialiser la configuration ?")
+            let confirmed = Confirm::new(&Message::ConfigResetConfirm.to_string())
+                .with_default(false)
                 .prompt()
-                .unwrap();
+                .map_err(|_| {
+                    ClientError::InvalidArgument(Message::ErrorConfirmationRequired.to_string())
+                })?;
+            if !confirmed {
+                return Ok(());
+            }
+        }
+        if let Some(fbx_app) = &app.config.app {
+            let _ = app.profiles.forget_app_token(&app.profile, fbx_app);
         }
-        app.config.reset().unwrap();
-        println!("Configuration réinitialisée !");
+        app.config.reset();
+        info!("{}", Message::ConfigResetDone);
         Ok(())
     }
 
-    pub async fn show(_app: &mut App) -> Result<(), ClientError> {
-        let mut table = Table::new();
-        table.load_preset(UTF8_FULL)
-            .set_content_arrangement(ContentArrangement::Dynamic)
-            .set_width(180)
-            .set_header(vec![
-                Cell::new("Header1").add_attribute(Attribute::Bold),
-                Cell::new("Header2").add_attribute(Attribute::Bold).fg(Color::Green),
-                Cell::new("Header3"),
-            ])
-            .add_row(vec![
-                Cell::new("This is a bold text").add_attribute(Attribute::Bold),
-                Cell::new("This is a green text").fg(Color::Green),
-                Cell::new("This one has black background").bg(Color::Black),
-            ])
-            .add_row(vec![
-                Cell::new("Blinky boi").add_attribute(Attribute::SlowBlink),
-                Cell::new("This table's content is dynamically arranged. The table is exactly 80 characters wide.\nHere comes a reallylongwordthatshoulddynamicallywrap"),
-                Cell::new("COMBINE ALL THE THINGS")
-                    .fg(Color::Green)
-                    .bg(Color::Black)
-                    .add_attributes(vec![
-                        Attribute::Bold,
-                        Attribute::SlowBlink,
-                    ])
+    pub async fn set_locale(app: &mut App, locale: Locale) -> Result<(), ClientError> {
+        app.config.pref.locale = Some(locale);
+        i18n::init(locale);
+        info!("{}", Message::ConfigLocaleUpdated(locale));
+        Ok(())
+    }
+
+    /// Switches to `https://{api_domain}:{https_port}/` once the box has
+    /// answered over HTTPS with a certificate signed by the Freebox root CAs,
+    /// or by those of `--ca-file`.
+    pub async fn enable_https(
+        app: &mut App,
+        args: &ConfigHttpsEnableArgs,
+    ) -> Result<(), ClientError> {
+        let version = app.box_version().await?;
+        if !version.https_available || version.api_domain.is_empty() {
+            return Err(ClientError::RequestError(
+                Message::HttpsUnavailable.to_string(),
+            ));
+        }
+        let ca_file = args.ca_file.clone().or(app.config.pref.ca_file.clone());
+        let pem_bundle = root_certificates(ca_file.as_deref())?;
+        let url = format!("https://{}:{}/", version.api_domain, version.https_port);
+        let mut client = app.client.clone();
+        client.set_root_certificates(&pem_bundle)?;
+        client.set_full_url(url.trim_end_matches('/').to_string());
+        let secure_version = app.api.api_version(&client).await?;
+        if secure_version.uid != version.uid {
+            return Err(ClientError::InvalidArgument(
+                Message::ErrorUnexpectedHttpsBox.to_string(),
+            ));
+        }
+
+        let pref = &mut app.config.pref;
+        if pref.base_url.starts_with("http://") {
+            pref.http_url = Some(pref.base_url.clone());
+        }
+        pref.set_base_url(url.clone());
+        pref.version = major_version(&secure_version.api_version)?;
+        pref.api_base_url = Some(secure_version.api_base_url);
+        pref.ca_file = ca_file.map(absolute_path);
+        info!("{}", Message::HttpsEnabled(&url));
+        Ok(())
+    }
+
+    pub async fn disable_https(app: &mut App) -> Result<(), ClientError> {
+        let pref = &mut app.config.pref;
+        let base_url = pref
+            .http_url
+            .take()
+            .unwrap_or(FbxPreferences::default().base_url);
+        pref.set_base_url(base_url);
+        pref.ca_file = None;
+        info!("{}", Message::HttpsDisabled);
+        Ok(())
+    }
+
+    pub async fn enable_remote(app: &mut App) -> Result<(), ClientError> {
+        let Some(remote_url) = app.config.pref.remote_url.clone() else {
+            return Err(ClientError::InvalidArgument(
+                Message::RemoteNotConfigured.to_string(),
+            ));
+        };
+        // Checked now, every command would fail in remote mode otherwise.
+        if remote_url.starts_with("https://") {
+            parse_root_certificates(&root_certificates(app.config.pref.ca_file.as_deref())?)?;
+        }
+        app.config.pref.remote = true;
+        info!("{}", Message::RemoteModeEnabled(&remote_url));
+        Ok(())
+    }
+
+    pub async fn disable_remote(app: &mut App) -> Result<(), ClientError> {
+        app.config.pref.remote = false;
+        info!("{}", Message::RemoteModeDisabled);
+        Ok(())
+    }
+
+    pub async fn list_profiles(app: &mut App, args: &ListArgs) -> Result<(), ClientError> {
+        let mut profiles = app.profiles.profiles.clone();
+        profiles.insert(app.profile.clone(), app.config.clone());
+        let rows: Vec<ProfileRow> = profiles
+            .iter()
+            .map(|(name, profile)| ProfileRow {
+                name: name.clone(),
+                url: profile.pref.url().to_string(),
+                app_id: profile.app.as_ref().map(|app| app.app_id.clone()),
+                app_status: match profile.app.as_ref().map(|app| &app.status) {
+                    Some(FbxAppStatus::Granted(_) | FbxAppStatus::Stored) => "granted",
+                    Some(FbxAppStatus::Pending) => "pending",
+                    None => "none",
+                },
+                current: *name == app.profiles.current,
+            })
+            .collect();
+        output::list(app.cli.output, args, &rows, |rows| {
+            let mut table = new_table(vec![
+                Message::ProfileName.to_string(),
+                Message::ProfileUrl.to_string(),
+                Message::ProfileApp.to_string(),
+                Message::ProfileCurrent.to_string(),
             ]);
+            for row in rows {
+                let status = match row.app_status {
+                    "granted" => Message::ProfileAppGranted,
+                    "pending" => Message::ProfileAppPending,
+                    _ => Message::ProfileAppNone,
+                };
+                table.add_row(vec![
+                    Cell::new(&row.name),
+                    Cell::new(&row.url),
+                    Cell::new(status),
+                    Cell::new(if row.current { "*" } else { "" }),
+                ]);
+            }
+            table
+        })
+    }
 
-        println!("{table}");
+    pub async fn add_profile(
+        app: &mut App,
+        args: &ConfigProfileAddArgs,
+    ) -> Result<(), ClientError> {
+        if args.name == app.profile || app.profiles.profile(&args.name).is_some() {
+            return Err(ClientError::InvalidArgument(
+                Message::ProfileExists(&args.name).to_string(),
+            ));
+        }
+        let mut profile = FbxProfile::default();
+        if let Some(url) = &args.url {
+            let url = Url::parse(url)
+                .map_err(|_| ClientError::InvalidUrl(Message::ConfigInvalidUrl.to_string()))?;
+            profile.pref.set_base_url(url.to_string());
+        }
+        app.profiles.profiles.insert(args.name.clone(), profile);
+        info!("{}", Message::ProfileAdded(&args.name));
+        Ok(())
+    }
+
+    pub async fn use_profile(app: &mut App, name: &str) -> Result<(), ClientError> {
+        if name != app.profile && app.profiles.profile(name).is_none() {
+            return Err(ClientError::NotFound(
+                Message::ProfileUnknown(name).to_string(),
+            ));
+        }
+        app.profiles.current = name.to_string();
+        info!("{}", Message::ProfileUsed(name));
         Ok(())
     }
+
+    pub async fn remove_profile(
+        app: &mut App,
+        args: &ConfigProfileRmArgs,
+    ) -> Result<(), ClientError> {
+        if args.name == app.profile || args.name == app.profiles.current {
+            return Err(ClientError::InvalidArgument(
+                Message::ProfileInUse(&args.name).to_string(),
+            ));
+        }
+        let Some(profile) = app.profiles.profile(&args.name) else {
+            return Err(ClientError::NotFound(
+                Message::ProfileUnknown(&args.name).to_string(),
+            ));
+        };
+        if !args.yes {
+            let confirmed = Confirm::new(&Message::ProfileRemoveConfirm(&args.name).to_string())
+                .with_default(false)
+                .prompt()
+                .unwrap_or(false);
+            if !confirmed {
+                return Ok(());
+            }
+        }
+        if let Some(fbx_app) = &profile.app {
+            app.profiles.forget_app_token(&args.name, fbx_app)?;
+        }
+        app.profiles.profiles.remove(&args.name);
+        info!("{}", Message::ProfileRemoved(&args.name));
+        Ok(())
+    }
+
+    /// Shows the active profile, without any secret.
+    pub async fn show(app: &mut App) -> Result<(), ClientError> {
+        let pref = &app.config.pref;
+        let info = ConfigInfo {
+            profile: app.profile.clone(),
+            file: app.profiles.location().to_string_lossy().to_string(),
+            url: pref.url().to_string(),
+            api_version: pref.version.to_string(),
+            api_base_url: pref.api_base_url().to_string(),
+            locale: pref.locale,
+            https: pref.is_https(),
+            ca_file: pref.ca_file.clone(),
+            remote: pref.remote,
+            remote_url: pref.remote_url.clone(),
+            secrets: match &app.config.app {
+                Some(fbx_app) => app.profiles.backend(fbx_app),
+                None => app.profiles.secrets,
+            },
+            app_id: app.config.app.as_ref().map(|app| app.app_id.clone()),
+        };
+        let table = output::fields_table(&info)?;
+        output::item(app.cli.output, &info, |_| println!("{table}"))
+    }
+}
+
+#[derive(Clone, Serialize)]
+struct ProfileRow {
+    name: String,
+    url: String,
+    app_id: Option<String>,
+    app_status: &'static str,
+    current: bool,
+}
+
+#[derive(Serialize)]
+struct ConfigInfo {
+    profile: String,
+    file: String,
+    url: String,
+    api_version: String,
+    api_base_url: String,
+    locale: Option<Locale>,
+    https: bool,
+    ca_file: Option<String>,
+    remote: bool,
+    remote_url: Option<String>,
+    secrets: SecretBackend,
+    app_id: Option<String>,
+}
+
+/// Stores paths given on the command line independently of the current
+/// directory.
+pub fn absolute_path(path: String) -> String {
+    std::fs::canonicalize(&path)
+        .map(|path| path.to_string_lossy().to_string())
+        .unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::FbxPreferences;
+    use crate::fake::{fixture_path, FakeFreebox, APP_TOKEN};
+    use crate::models::args::Cli;
+    use crate::models::exception::ClientError;
+    use crate::run;
+    use clap::Parser;
+    use serde_json::json;
+    use url::Url;
+
+    #[tokio::test]
+    async fn set_url_needs_no_box() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx
+            .app(&["config", "set-url", "http://192.168.1.254/"])
+            .await;
+
+        run(&mut app).await.unwrap();
+
+        assert_eq!(app.config.pref.url(), "http://192.168.1.254/");
+        assert!(fbx.sessions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn set_url_forgets_the_previous_box() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["lcd", "get"]).await;
+        run(&mut app).await.unwrap();
+        let profile = app.profile.clone();
+        app.profiles.store_app_token(&profile, APP_TOKEN).unwrap();
+        let fbx_app = app.config.app.clone().unwrap();
+        app.cli = Cli::parse_from(["fbx", "config", "set-url", "http://192.168.1.254/"]);
+
+        run(&mut app).await.unwrap();
+
+        assert!(app.config.session.is_none());
+        assert!(app.config.app.is_none());
+        let app_token = app.profiles.load_app_token(&profile, &fbx_app).unwrap();
+        assert_eq!(app_token, None);
+    }
+
+    #[tokio::test]
+    async fn set_url_keeps_the_session_on_the_same_box() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["lcd", "get"]).await;
+        run(&mut app).await.unwrap();
+        let url = Url::parse(&fbx.url).unwrap();
+        let other_port = format!("http://{}:9/", url.host_str().unwrap());
+        app.cli = Cli::parse_from(["fbx", "config", "set-url", &other_port]);
+
+        run(&mut app).await.unwrap();
+
+        assert!(app.config.session.is_some());
+        assert!(app.config.app.is_some());
+    }
+
+    #[tokio::test]
+    async fn profiles_are_added_used_and_removed() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx
+            .app(&[
+                "config",
+                "profile",
+                "add",
+                "maison",
+                "--url",
+                "http://192.168.1.254/",
+            ])
+            .await;
+        run(&mut app).await.unwrap();
+        assert!(app.profiles.profile("maison").is_some());
+
+        app.cli = Cli::parse_from(["fbx", "config", "profile", "use", "maison"]);
+        run(&mut app).await.unwrap();
+        assert_eq!(app.profiles.current, "maison");
+
+        app.cli = Cli::parse_from(["fbx", "config", "profile", "rm", "maison", "-y"]);
+        let result = run(&mut app).await;
+        assert!(matches!(result, Err(ClientError::InvalidArgument(_))));
+        assert!(app.profiles.profile("maison").is_some());
+
+        app.profiles.current = app.profile.clone();
+        run(&mut app).await.unwrap();
+        assert!(app.profiles.profile("maison").is_none());
+
+        let result = run(&mut app).await;
+        assert!(matches!(result, Err(ClientError::NotFound(_))));
+        app.cli = Cli::parse_from(["fbx", "config", "profile", "use", "maison"]);
+        let result = run(&mut app).await;
+        assert!(matches!(result, Err(ClientError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn reset_without_a_terminal_needs_confirm() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["config", "reset"]).await;
+
+        let result = run(&mut app).await;
+
+        assert!(matches!(result, Err(ClientError::InvalidArgument(_))));
+        assert!(app.config.app.is_some());
+    }
+
+    #[tokio::test]
+    async fn invalid_urls_are_rejected() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["config", "set-url", "pas une url"]).await;
+
+        let result = run(&mut app).await;
+
+        assert!(matches!(result, Err(ClientError::InvalidUrl(_))));
+    }
+
+    #[tokio::test]
+    async fn remote_mode_needs_a_remote_url() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["config", "remote", "enable"]).await;
+
+        let result = run(&mut app).await;
+
+        assert!(matches!(result, Err(ClientError::InvalidArgument(_))));
+        assert!(!app.config.pref.remote);
+    }
+
+    #[tokio::test]
+    async fn remote_mode_is_enabled_and_disabled() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["config", "remote", "enable"]).await;
+        app.config.pref.remote_url = Some("https://x3mcu3ka.fbxos.fr:3615/".to_string());
+        app.config.pref.ca_file = Some(fixture_path("test_root_ca.pem"));
+
+        run(&mut app).await.unwrap();
+        assert!(app.config.pref.remote);
+
+        app.cli = Cli::parse_from(["fbx", "config", "remote", "disable"]);
+        run(&mut app).await.unwrap();
+        assert!(!app.config.pref.remote);
+    }
+
+    #[tokio::test]
+    async fn remote_mode_needs_root_certificates() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["config", "remote", "enable"]).await;
+        app.config.pref.remote_url = Some("https://x3mcu3ka.fbxos.fr:35480/".to_string());
+
+        let result = run(&mut app).await;
+        assert!(matches!(result, Err(ClientError::InvalidArgument(_))));
+        assert!(!app.config.pref.remote);
+
+        app.config.pref.ca_file = Some(fixture_path("test_root_ca.pem"));
+        run(&mut app).await.unwrap();
+        assert!(app.config.pref.remote);
+    }
+
+    #[tokio::test]
+    async fn remote_mode_targets_the_remote_url() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["config", "remote", "enable"]).await;
+        app.config
+            .pref
+            .set_base_url("http://127.0.0.1:9/".to_string());
+        app.config.pref.remote_url = Some(fbx.url.clone());
+        run(&mut app).await.unwrap();
+
+        app.cli = Cli::parse_from(["fbx", "lcd", "get"]);
+        app.initialize().await.unwrap();
+        run(&mut app).await.unwrap();
+
+        assert_eq!(fbx.requests("GET", "/lcd/config/").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn https_needs_a_ca_file_without_bundled_certificates() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["config", "https", "enable"]).await;
+
+        let result = run(&mut app).await;
+
+        assert!(matches!(result, Err(ClientError::InvalidArgument(_))));
+        assert_eq!(app.config.pref.url(), fbx.url);
+        assert_eq!(fbx.requests("GET", "/api_version/").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn https_rejects_a_missing_ca_file() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx
+            .app(&[
+                "config",
+                "https",
+                "enable",
+                "--ca-file",
+                "/nonexistent/ca.pem",
+            ])
+            .await;
+
+        let result = run(&mut app).await;
+
+        assert!(matches!(result, Err(ClientError::InvalidArgument(_))));
+        assert_eq!(app.config.pref.url(), fbx.url);
+        assert!(app.config.pref.ca_file.is_none());
+    }
+
+    #[tokio::test]
+    async fn https_needs_a_box_with_https() {
+        let fbx = FakeFreebox::start();
+        fbx.answer(
+            "GET",
+            "/api_version/",
+            200,
+            json!({
+                "uid": "23b86ec8091013d668829fe12791fdab",
+                "api_domain": "",
+                "https_available": false,
+                "https_port": 0,
+                "api_base_url": "/api/",
+                "api_version": "8.0",
+                "device_type": "FreeboxServer7,1",
+                "box_model_name": "Freebox v7 (r1)",
+            }),
+        );
+        let mut app = fbx.app(&["config", "https", "enable"]).await;
+
+        let result = run(&mut app).await;
+
+        assert!(matches!(result, Err(ClientError::RequestError(_))));
+        assert_eq!(app.config.pref.url(), fbx.url);
+    }
+
+    #[tokio::test]
+    async fn https_disable_restores_the_previous_url() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["config", "https", "disable"]).await;
+        let pref = &mut app.config.pref;
+        pref.http_url = Some("http://192.168.1.254/".to_string());
+        pref.set_base_url("https://x3mcu3ka.fbxos.fr:3615/".to_string());
+        pref.ca_file = Some(fixture_path("test_root_ca.pem"));
+
+        run(&mut app).await.unwrap();
+
+        assert_eq!(app.config.pref.url(), "http://192.168.1.254/");
+        assert!(app.config.pref.http_url.is_none());
+        assert!(app.config.pref.ca_file.is_none());
+        assert!(fbx.requests("GET", "/api_version/").is_empty());
+    }
+
+    #[tokio::test]
+    async fn https_disable_defaults_to_the_freebox_url() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["config", "https", "disable"]).await;
+
+        run(&mut app).await.unwrap();
+
+        assert_eq!(
+            app.config.pref.url(),
+            FbxPreferences::default().base_url.as_str()
+        );
+    }
 }
@@ -0,0 +1,167 @@
+use crate::app::{read_ca_file, App};
+use crate::client::parse_root_certificates;
+use crate::handlers::config::absolute_path;
+use crate::i18n::Message;
+use crate::info;
+use crate::models::args::ConnectionRemoteApiEnableArgs;
+use crate::models::exception::ClientError;
+use crate::models::freebox::authorization::Permission;
+use crate::models::freebox::connection::{ConnectionConfiguration, ConnectionConfigurationBody};
+use crate::output;
+use crate::services::api::FreeboxOSApiCalls;
+
+pub struct Connection;
+
+impl Connection {
+    pub async fn remote_api(app: &mut App) -> Result<(), ClientError> {
+        let session_token = app.session_token().await?;
+        let config = app
+            .api
+            .connection_config(&app.client, session_token)
+            .await?
+            .result;
+        let remote_url = app.config.pref.remote_url.as_deref();
+        output::item(app.cli.output, &config, |config| {
+            Self::print(config);
+            if let Some(remote_url) = remote_url {
+                println!("{}", Message::RemoteEndpoint(remote_url));
+            }
+        })
+    }
+
+    /// Turns on the remote API access from inside the LAN, and records the
+    /// public endpoint so `fbx config remote enable` can target it later.
+    pub async fn enable_remote_api(
+        app: &mut App,
+        args: &ConnectionRemoteApiEnableArgs,
+    ) -> Result<(), ClientError> {
+        let version = app.box_version().await?;
+        if !version.https_available || version.api_domain.is_empty() {
+            return Err(ClientError::RequestError(
+                Message::HttpsUnavailable.to_string(),
+            ));
+        }
+        if let Some(ca_file) = &args.ca_file {
+            parse_root_certificates(&read_ca_file(ca_file)?)?;
+        }
+        let session_token = app.session_token_for(Permission::Settings).await?;
+        let config = app
+            .api
+            .update_connection_config(
+                &app.client,
+                session_token,
+                ConnectionConfigurationBody::remote_api(true),
+            )
+            .await?
+            .result;
+
+        let remote_url = format!(
+            "https://{}:{}/",
+            version.api_domain, config.remote_access_port
+        );
+        let pref = &mut app.config.pref;
+        pref.remote_url = Some(remote_url.clone());
+        if let Some(ca_file) = args.ca_file.clone() {
+            pref.ca_file = Some(absolute_path(ca_file));
+        }
+        output::item(app.cli.output, &config, |config| {
+            info!("{}", Message::RemoteApiEnabled(&remote_url));
+            Self::print(config)
+        })
+    }
+
+    pub async fn disable_remote_api(app: &mut App) -> Result<(), ClientError> {
+        let session_token = app.session_token_for(Permission::Settings).await?;
+        let config = app
+            .api
+            .update_connection_config(
+                &app.client,
+                session_token,
+                ConnectionConfigurationBody::remote_api(false),
+            )
+            .await?
+            .result;
+        let pref = &mut app.config.pref;
+        pref.remote = false;
+        pref.remote_url = None;
+        output::item(app.cli.output, &config, |config| {
+            info!("{}", Message::RemoteApiDisabled);
+            Self::print(config)
+        })
+    }
+
+    fn print(config: &ConnectionConfiguration) {
+        println!("{}", Message::RemoteAccess(config.remote_access));
+        println!("{}", Message::RemoteApiAccess(config.api_remote_access));
+        println!("{}", Message::RemoteIp(&config.remote_access_ip));
+        println!("{}", Message::RemotePort(config.remote_access_port));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fake::FakeFreebox;
+    use crate::models::exception::ClientError;
+    use crate::output::captured;
+    use crate::run;
+    use serde_json::{json, Value};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn enable_records_the_remote_url() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx
+            .app(&["--output", "json", "connection", "remote-api", "enable"])
+            .await;
+
+        run(&mut app).await.unwrap();
+
+        let config: Value = serde_json::from_str(&captured()).unwrap();
+        assert_eq!(config["remote_access_port"], json!(35480));
+
+        let requests = fbx.requests("PUT", "/connection/config/");
+        assert_eq!(
+            requests[0].body,
+            json!({"remote_access": true, "api_remote_access": true})
+        );
+        assert_eq!(
+            app.config.pref.remote_url.as_deref(),
+            Some("https://x3mcu3ka.fbxos.fr:35480/")
+        );
+    }
+
+    #[tokio::test]
+    async fn disable_forgets_the_remote_url() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["connection", "remote-api", "disable"]).await;
+        app.config.pref.remote_url = Some("https://x3mcu3ka.fbxos.fr:35480/".to_string());
+
+        run(&mut app).await.unwrap();
+
+        let requests = fbx.requests("PUT", "/connection/config/");
+        assert_eq!(requests[0].body, json!({"api_remote_access": false}));
+        assert!(app.config.pref.remote_url.is_none());
+    }
+
+    #[tokio::test]
+    async fn enable_rejects_an_invalid_ca_file() {
+        let fbx = FakeFreebox::start();
+        let directory = TempDir::new().unwrap();
+        let ca_file = directory.path().join("ca.pem");
+        std::fs::write(&ca_file, "pas un certificat").unwrap();
+        let ca_file = ca_file.to_str().unwrap();
+
+        for ca_file in [ca_file, "/nonexistent/ca.pem"] {
+            let mut app = fbx
+                .app(&["connection", "remote-api", "enable", "--ca-file", ca_file])
+                .await;
+
+            let result = run(&mut app).await;
+
+            assert!(matches!(result, Err(ClientError::InvalidArgument(_))));
+            assert!(app.config.pref.remote_url.is_none());
+            assert!(app.config.pref.ca_file.is_none());
+        }
+        assert!(fbx.requests("PUT", "/connection/config/").is_empty());
+    }
+}
@@ -1,11 +1,94 @@
 use crate::app::App;
+use crate::handlers::new_table;
+use crate::i18n::Message;
+use crate::models::args::ListArgs;
 use crate::models::exception::ClientError;
+use crate::output;
+use crate::services::api::FreeboxOSApiCalls;
+use comfy_table::{Cell, Color};
+
+/// Hosts of the main LAN, the only interface of a box in router mode.
+const LAN_INTERFACE: &str = "pub";
 
 pub struct Device;
 
 impl Device {
-    pub async fn list(_app: &mut App) -> Result<(), ClientError> {
-        println!("List devices");
-        Ok(())
+    pub async fn list(app: &mut App, args: &ListArgs) -> Result<(), ClientError> {
+        let session_token = app.session_token().await?;
+        let hosts = app
+            .api
+            .lan_hosts(&app.client, session_token, LAN_INTERFACE)
+            .await?
+            .result
+            .unwrap_or_default();
+        output::list(app.cli.output, args, &hosts, |hosts| {
+            let mut table = new_table(vec![
+                Message::DeviceName.to_string(),
+                Message::DeviceType.to_string(),
+                Message::DeviceIp.to_string(),
+                Message::DeviceMac.to_string(),
+                Message::DeviceVendor.to_string(),
+                Message::DeviceActive.to_string(),
+            ]);
+            for host in hosts {
+                table.add_row(vec![
+                    Cell::new(&host.primary_name),
+                    Cell::new(&host.host_type),
+                    Cell::new(host.ipv4().unwrap_or_default()),
+                    Cell::new(&host.l2ident.id),
+                    Cell::new(&host.vendor_name),
+                    Cell::new(host.active).fg(if host.active {
+                        Color::Green
+                    } else {
+                        Color::DarkGrey
+                    }),
+                ]);
+            }
+            table
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fake::FakeFreebox;
+    use crate::models::exception::ClientError;
+    use crate::output::captured;
+    use crate::run;
+    use serde_json::{json, Value};
+
+    #[tokio::test]
+    async fn list_filters_by_field() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx
+            .app(&[
+                "--output",
+                "json",
+                "device",
+                "list",
+                "--filter",
+                "type=smartphone",
+                "--columns",
+                "primary_name,host_type",
+            ])
+            .await;
+
+        run(&mut app).await.unwrap();
+
+        let devices: Value = serde_json::from_str(&captured()).unwrap();
+        assert_eq!(
+            devices,
+            json!([{"primary_name": "Pixel", "host_type": "smartphone"}])
+        );
+    }
+
+    #[tokio::test]
+    async fn list_rejects_unknown_columns() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["device", "list", "--columns", "colour"]).await;
+
+        let result = run(&mut app).await;
+
+        assert!(matches!(result, Err(ClientError::InvalidArgument(_))));
     }
 }
@@ -0,0 +1,106 @@
+use crate::app::App;
+use crate::handlers::new_table;
+use crate::i18n::Message;
+use crate::models::args::DhcpLeasesArgs;
+use crate::models::exception::ClientError;
+use crate::output;
+use crate::services::api::FreeboxOSApiCalls;
+use comfy_table::Cell;
+
+pub struct Dhcp;
+
+impl Dhcp {
+    pub async fn leases(app: &mut App, args: &DhcpLeasesArgs) -> Result<(), ClientError> {
+        let session_token = app.session_token().await?;
+        if args.static_leases {
+            let leases = app
+                .api
+                .dhcp_static_leases(&app.client, session_token)
+                .await?
+                .result
+                .unwrap_or_default();
+            return output::list(app.cli.output, &args.list, &leases, |leases| {
+                let mut table = new_table(vec![
+                    Message::DhcpHostname.to_string(),
+                    Message::DeviceIp.to_string(),
+                    Message::DeviceMac.to_string(),
+                    Message::DhcpComment.to_string(),
+                ]);
+                for lease in leases {
+                    table.add_row(vec![
+                        Cell::new(&lease.hostname),
+                        Cell::new(&lease.ip),
+                        Cell::new(&lease.mac),
+                        Cell::new(&lease.comment),
+                    ]);
+                }
+                table
+            });
+        }
+        let leases = app
+            .api
+            .dhcp_dynamic_leases(&app.client, session_token)
+            .await?
+            .result
+            .unwrap_or_default();
+        output::list(app.cli.output, &args.list, &leases, |leases| {
+            let mut table = new_table(vec![
+                Message::DhcpHostname.to_string(),
+                Message::DeviceIp.to_string(),
+                Message::DeviceMac.to_string(),
+                Message::DhcpStatic.to_string(),
+                Message::DhcpRemaining.to_string(),
+            ]);
+            for lease in leases {
+                table.add_row(vec![
+                    Cell::new(&lease.hostname),
+                    Cell::new(&lease.ip),
+                    Cell::new(&lease.mac),
+                    Cell::new(lease.is_static),
+                    Cell::new(format!(
+                        "{} h {:02} min",
+                        lease.lease_remaining / 3600,
+                        lease.lease_remaining % 3600 / 60
+                    )),
+                ]);
+            }
+            table
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fake::FakeFreebox;
+    use crate::output::captured;
+    use crate::run;
+
+    #[tokio::test]
+    async fn leases_are_dynamic_by_default() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["dhcp", "leases"]).await;
+
+        run(&mut app).await.unwrap();
+
+        assert_eq!(fbx.requests("GET", "/dhcp/dynamic_lease/").len(), 1);
+        assert!(fbx.requests("GET", "/dhcp/static_lease/").is_empty());
+    }
+
+    #[tokio::test]
+    async fn static_leases() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx
+            .app(&["--output", "csv", "dhcp", "leases", "--static"])
+            .await;
+
+        run(&mut app).await.unwrap();
+
+        let csv = captured();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].split(',').any(|column| column == "ip"));
+        assert!(lines[1].contains("192.168.1.2"));
+        assert!(lines[1].contains("nas"));
+        assert_eq!(fbx.requests("GET", "/dhcp/static_lease/").len(), 1);
+    }
+}
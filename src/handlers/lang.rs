@@ -0,0 +1,93 @@
+use crate::app::App;
+use crate::i18n::Message;
+use crate::info;
+use crate::models::args::LangSetArgs;
+use crate::models::exception::ClientError;
+use crate::models::freebox::authorization::Permission;
+use crate::models::freebox::language::LanguageSupportUpdateBody;
+use crate::output;
+use crate::services::api::FreeboxOSApiCalls;
+
+pub struct Lang;
+
+impl Lang {
+    pub async fn get(app: &mut App) -> Result<(), ClientError> {
+        let session_token = app.session_token().await?;
+        let language = app.api.language(&app.client, session_token).await?.result;
+        output::item(app.cli.output, &language, |language| {
+            println!("{}", Message::LangCurrent(&language.lang));
+            println!("{}", Message::LangAvailable(&language.available.join(", ")));
+        })
+    }
+
+    pub async fn set(app: &mut App, args: &LangSetArgs) -> Result<(), ClientError> {
+        let session_token = app.session_token_for(Permission::Settings).await?;
+        let language = app
+            .api
+            .language(&app.client, session_token.clone())
+            .await?
+            .result;
+        if !language.available.contains(&args.lang) {
+            return Err(ClientError::InvalidArgument(format!(
+                "{} {}",
+                Message::ErrorLangUnavailable(&args.lang),
+                Message::LangAvailable(&language.available.join(", "))
+            )));
+        }
+        if language.lang == args.lang {
+            info!("{}", Message::LangAlreadySet(&args.lang));
+            return Ok(());
+        }
+        let body = LanguageSupportUpdateBody {
+            lang: args.lang.clone(),
+        };
+        app.api
+            .update_language(&app.client, session_token, body)
+            .await?;
+        info!("{}", Message::LangUpdated(&args.lang));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fake::FakeFreebox;
+    use crate::models::exception::ClientError;
+    use crate::run;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn set_changes_the_language() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["lang", "set", "eng"]).await;
+
+        run(&mut app).await.unwrap();
+
+        let requests = fbx.requests("POST", "/lang/");
+        assert_eq!(requests[0].body, json!({"lang": "eng"}));
+    }
+
+    #[tokio::test]
+    async fn set_keeps_the_current_language() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["lang", "set", "fra"]).await;
+
+        run(&mut app).await.unwrap();
+
+        assert!(fbx.requests("POST", "/lang/").is_empty());
+    }
+
+    #[tokio::test]
+    async fn set_rejects_unavailable_languages() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["lang", "set", "xyz"]).await;
+
+        let result = run(&mut app).await;
+
+        match result {
+            Err(ClientError::InvalidArgument(message)) => assert!(message.contains("fra")),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(fbx.requests("POST", "/lang/").is_empty());
+    }
+}
@@ -0,0 +1,146 @@
+use crate::app::App;
+use crate::i18n::Message;
+use crate::info;
+use crate::models::args::LcdSetArgs;
+use crate::models::exception::ClientError;
+use crate::models::freebox::authorization::Permission;
+use crate::models::freebox::lcd::{LcdInfoResult, LcdUpdateBody};
+use crate::output;
+use crate::services::api::FreeboxOSApiCalls;
+
+const ORIENTATIONS: [i32; 4] = [0, 90, 180, 270];
+
+pub struct Lcd;
+
+impl Lcd {
+    pub async fn get(app: &mut App) -> Result<(), ClientError> {
+        let session_token = app.session_token().await?;
+        let config = app.api.lcd_config(&app.client, session_token).await?.result;
+        output::item(app.cli.output, &config, Self::print)
+    }
+
+    pub async fn set(app: &mut App, args: &LcdSetArgs) -> Result<(), ClientError> {
+        if args.brightness.is_none() && args.orientation.is_none() && args.hide_wifi_key.is_none() {
+            return Err(ClientError::InvalidArgument(
+                Message::ErrorNothingToUpdate("--brightness, --orientation, --hide-wifi-key")
+                    .to_string(),
+            ));
+        }
+        if let Some(orientation) = args.orientation {
+            if !ORIENTATIONS.contains(&orientation) {
+                return Err(ClientError::InvalidArgument(
+                    Message::ErrorLcdOrientation.to_string(),
+                ));
+            }
+        }
+        let session_token = app.session_token_for(Permission::Settings).await?;
+        if args.orientation.is_some() {
+            let current = app
+                .api
+                .lcd_config(&app.client, session_token.clone())
+                .await?
+                .result;
+            if current.orientation_forced {
+                return Err(ClientError::InvalidArgument(
+                    Message::ErrorLcdOrientationForced.to_string(),
+                ));
+            }
+        }
+        let body = LcdUpdateBody {
+            brightness: args.brightness,
+            orientation: args.orientation,
+            hide_wifi_key: args.hide_wifi_key,
+        };
+        let config = app
+            .api
+            .update_lcd_config(&app.client, session_token, body)
+            .await?
+            .result;
+        output::item(app.cli.output, &config, |config| {
+            info!("{}", Message::LcdUpdated);
+            Self::print(config)
+        })
+    }
+
+    fn print(config: &LcdInfoResult) {
+        println!("{}", Message::LcdBrightness(config.brightness));
+        println!(
+            "{}",
+            Message::LcdOrientation(config.orientation, config.orientation_forced)
+        );
+        println!("{}", Message::LcdHideWifiKey(config.hide_wifi_key));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fake::FakeFreebox;
+    use crate::models::exception::ClientError;
+    use crate::output::captured;
+    use crate::run;
+    use serde_json::{json, Value};
+
+    #[tokio::test]
+    async fn get_opens_a_session() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["lcd", "get"]).await;
+
+        run(&mut app).await.unwrap();
+
+        assert_eq!(fbx.sessions(), vec!["session-1".to_string()]);
+        let requests = fbx.requests("GET", "/lcd/config/");
+        assert_eq!(requests[0].session_token.as_deref(), Some("session-1"));
+    }
+
+    #[tokio::test]
+    async fn set_sends_only_the_given_fields() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx
+            .app(&["--output", "json", "lcd", "set", "--brightness", "40"])
+            .await;
+
+        run(&mut app).await.unwrap();
+
+        let requests = fbx.requests("PUT", "/lcd/config/");
+        assert_eq!(requests[0].body, json!({"brightness": 40}));
+        let config: Value = serde_json::from_str(&captured()).unwrap();
+        assert!(config.get("brightness").is_some());
+    }
+
+    #[tokio::test]
+    async fn forced_orientation_is_not_changed() {
+        let fbx = FakeFreebox::start();
+        fbx.answer(
+            "GET",
+            "/lcd/config/",
+            200,
+            json!({
+                "success": true,
+                "result": {
+                    "brightness": 75,
+                    "orientation": 90,
+                    "orientation_forced": true,
+                    "hide_wifi_key": false
+                }
+            }),
+        );
+        let mut app = fbx.app(&["lcd", "set", "-o", "180"]).await;
+
+        let result = run(&mut app).await;
+
+        assert!(matches!(result, Err(ClientError::InvalidArgument(_))));
+        assert!(fbx.requests("PUT", "/lcd/config/").is_empty());
+    }
+
+    #[tokio::test]
+    async fn invalid_orientation_is_refused_before_any_request() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["lcd", "set", "-o", "45"]).await;
+
+        let result = run(&mut app).await;
+
+        assert!(matches!(result, Err(ClientError::InvalidArgument(_))));
+        assert!(fbx.sessions().is_empty());
+        assert!(fbx.requests("GET", "/lcd/config/").is_empty());
+    }
+}
@@ -1,7 +1,44 @@
+pub mod air_media;
 pub mod auth;
 pub mod config;
+pub mod connection;
 pub mod device;
+pub mod dhcp;
+pub mod lang;
+pub mod lcd;
 pub mod notification;
+pub mod redirection;
 pub mod settings;
 pub mod system;
 pub mod vm;
+pub mod wifi;
+
+use crate::logging;
+use comfy_table::presets::UTF8_FULL;
+use comfy_table::{Attribute, Cell, ContentArrangement, Table};
+
+pub fn new_table(header: Vec<String>) -> Table {
+    let mut table = new_headless_table();
+    table.set_header(
+        header
+            .into_iter()
+            .map(|h| Cell::new(h).add_attribute(Attribute::Bold)),
+    );
+    table
+}
+
+pub fn new_headless_table() -> Table {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic);
+    if logging::no_color() {
+        // Forcing no tty also drops the terminal width, keep it for wrapping.
+        let width = table.width();
+        table.force_no_tty();
+        if let Some(width) = width {
+            table.set_width(width);
+        }
+    }
+    table
+}
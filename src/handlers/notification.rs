@@ -1 +1,342 @@
+use crate::app::App;
+use crate::handlers::new_table;
+use crate::i18n::Message;
+use crate::info;
+use crate::models::args::{
+    ListArgs, NotificationRegisterArgs, NotificationTargetsRmArgs,
+    NotificationTargetsUpdateSubscriptionsArgs,
+};
+use crate::models::exception::ClientError;
+use crate::models::freebox::authorization::Permission;
+use crate::models::freebox::notification::{
+    NotificationSend, NotificationTarget, NotificationTargetUpdateBody,
+};
+use crate::output;
+use crate::services::api::FreeboxOSApiCalls;
+use chrono::{DateTime, Duration, Utc};
+use comfy_table::Cell;
+use inquire::Confirm;
 
+pub struct Notification;
+
+impl Notification {
+    pub async fn list(app: &mut App, args: &ListArgs) -> Result<(), ClientError> {
+        let session_token = app.session_token().await?;
+        let targets = app
+            .api
+            .notification_targets(&app.client, session_token)
+            .await?
+            .result
+            .unwrap_or_default();
+        output::list(app.cli.output, args, &targets, |targets| {
+            let mut table = new_table(vec![
+                Message::NotificationId.to_string(),
+                Message::NotificationName.to_string(),
+                Message::NotificationType.to_string(),
+                Message::NotificationLastUse.to_string(),
+                Message::NotificationSubscriptions.to_string(),
+            ]);
+            for target in targets {
+                table.add_row(vec![
+                    Cell::new(&target.id),
+                    Cell::new(&target.name),
+                    Cell::new(&target.kind),
+                    Cell::new(Self::last_use(target)),
+                    Cell::new(target.subscriptions.join(", ")),
+                ]);
+            }
+            table
+        })
+    }
+
+    pub async fn remove(
+        app: &mut App,
+        args: &NotificationTargetsRmArgs,
+    ) -> Result<(), ClientError> {
+        let limit = args.unused_since.map(Self::unused_limit).transpose()?;
+        let session_token = app.session_token_for(Permission::Settings).await?;
+        let targets = app
+            .api
+            .notification_targets(&app.client, session_token.clone())
+            .await?
+            .result
+            .unwrap_or_default();
+        let targets: Vec<NotificationTarget> = match (&args.id, limit) {
+            (Some(id), _) => {
+                let target = targets.into_iter().find(|target| &target.id == id).ok_or(
+                    ClientError::NotFound(Message::ErrorNotificationTargetNotFound(id).to_string()),
+                )?;
+                vec![target]
+            }
+            (None, Some(limit)) => targets
+                .into_iter()
+                .filter(|target| target.last_use < limit)
+                .collect(),
+            (None, None) => {
+                return Err(ClientError::InvalidArgument(
+                    Message::ErrorNotificationTargetRequired.to_string(),
+                ))
+            }
+        };
+        if targets.is_empty() {
+            info!("{}", Message::NotificationNoTarget);
+            return Ok(());
+        }
+        if !args.yes {
+            for target in &targets {
+                println!(
+                    "{} ({}) - {}",
+                    target.name,
+                    target.id,
+                    Self::last_use(target)
+                );
+            }
+            let confirmed =
+                Confirm::new(&Message::NotificationRemoveConfirm(targets.len()).to_string())
+                    .with_default(false)
+                    .prompt()
+                    .unwrap_or(false);
+            if !confirmed {
+                return Ok(());
+            }
+        }
+        for target in targets {
+            app.api
+                .delete_notification_target(&app.client, session_token.clone(), &target.id)
+                .await?;
+            info!("{}", Message::NotificationRemoved(&target.name));
+        }
+        Ok(())
+    }
+
+    pub async fn update_subscriptions(
+        app: &mut App,
+        args: &NotificationTargetsUpdateSubscriptionsArgs,
+    ) -> Result<(), ClientError> {
+        let session_token = app.session_token_for(Permission::Settings).await?;
+        let body = NotificationTargetUpdateBody {
+            subscriptions: args.subscriptions.clone(),
+        };
+        let target = app
+            .api
+            .update_notification_target(&app.client, session_token, &args.id, body)
+            .await?
+            .result;
+        info!(
+            "{}",
+            Message::NotificationSubscriptionsUpdated(&target.name)
+        );
+        Ok(())
+    }
+
+    pub async fn register(
+        app: &mut App,
+        args: &NotificationRegisterArgs,
+    ) -> Result<(), ClientError> {
+        let session_token = app.session_token_for(Permission::Settings).await?;
+        let box_id = match &args.box_id {
+            Some(box_id) => box_id.clone(),
+            None => app.box_version().await?.uid,
+        };
+        let body = NotificationSend {
+            box_id,
+            device_type: args.device_type.clone(),
+            token: args.token.clone(),
+            device_name: args
+                .device_name
+                .clone()
+                .unwrap_or_else(|| format!("{} - {}", whoami::username(), whoami::distro())),
+            device_id: args.device_id.clone(),
+        };
+        let target = app
+            .api
+            .register_notification_target(&app.client, session_token, body)
+            .await?
+            .result;
+        info!("{}", Message::NotificationRegistered(&target.id));
+        Ok(())
+    }
+
+    fn last_use(target: &NotificationTarget) -> String {
+        match DateTime::from_timestamp(target.last_use, 0) {
+            Some(date) if target.last_use > 0 => date.format("%Y-%m-%d %H:%M").to_string(),
+            _ => Message::NotificationNever.to_string(),
+        }
+    }
+
+    /// Targets last used before this timestamp are considered unused.
+    fn unused_limit(days: i64) -> Result<i64, ClientError> {
+        Duration::try_days(days)
+            .and_then(|duration| Utc::now().checked_sub_signed(duration))
+            .map(|limit| limit.timestamp())
+            .ok_or_else(|| {
+                ClientError::InvalidArgument(Message::ErrorInvalidUnusedSince(days).to_string())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fake::FakeFreebox;
+    use crate::models::exception::ClientError;
+    use crate::run;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn rm_removes_the_target() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx
+            .app(&["notification", "targets", "rm", "c5e4a9d1", "-y"])
+            .await;
+
+        run(&mut app).await.unwrap();
+
+        assert_eq!(fbx.requests("DELETE", "/notif/targets/c5e4a9d1/").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn rm_unused_targets_only() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx
+            .app(&[
+                "notification",
+                "targets",
+                "rm",
+                "--unused-since",
+                "3650",
+                "-y",
+            ])
+            .await;
+
+        run(&mut app).await.unwrap();
+
+        assert_eq!(fbx.requests("DELETE", "/notif/targets/0b7f22e3/").len(), 1);
+        assert!(fbx
+            .requests("DELETE", "/notif/targets/c5e4a9d1/")
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn rm_refuses_an_out_of_range_duration() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx
+            .app(&[
+                "notification",
+                "targets",
+                "rm",
+                "--unused-since",
+                "9223372036854775807",
+                "-y",
+            ])
+            .await;
+
+        let result = run(&mut app).await;
+
+        assert!(matches!(result, Err(ClientError::InvalidArgument(_))));
+        assert!(fbx.sessions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn rm_unknown_target_is_not_found() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx
+            .app(&["notification", "targets", "rm", "ffffffff", "-y"])
+            .await;
+
+        let result = run(&mut app).await;
+
+        assert!(matches!(result, Err(ClientError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn update_subscriptions_replaces_them() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx
+            .app(&[
+                "notification",
+                "targets",
+                "update-subscriptions",
+                "c5e4a9d1",
+                "security",
+            ])
+            .await;
+
+        run(&mut app).await.unwrap();
+
+        let requests = fbx.requests("PUT", "/notif/targets/c5e4a9d1/");
+        assert_eq!(requests[0].body, json!({"subscriptions": ["security"]}));
+    }
+
+    #[tokio::test]
+    async fn register_uses_the_box_uid() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx
+            .app(&[
+                "notification",
+                "register",
+                "--device-id",
+                "pixel-8",
+                "--device-type",
+                "android",
+                "--token",
+                "push-token",
+                "--device-name",
+                "Pixel",
+            ])
+            .await;
+
+        run(&mut app).await.unwrap();
+
+        let requests = fbx.requests("POST", "/notif/targets/");
+        assert_eq!(
+            requests[0].body,
+            json!({
+                "box_id": "23b86ec8091013d668829fe12791fdab",
+                "device_type": "android",
+                "token": "push-token",
+                "device_name": "Pixel",
+                "device_id": "pixel-8"
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn changes_need_the_settings_permission() {
+        let fbx = FakeFreebox::start();
+        fbx.deny("settings");
+        let commands: [&[&str]; 3] = [
+            &["notification", "targets", "rm", "c5e4a9d1", "-y"],
+            &[
+                "notification",
+                "targets",
+                "update-subscriptions",
+                "c5e4a9d1",
+                "security",
+            ],
+            &[
+                "notification",
+                "register",
+                "--device-id",
+                "pixel-8",
+                "--device-type",
+                "android",
+                "--token",
+                "push-token",
+                "--device-name",
+                "Pixel",
+            ],
+        ];
+        for args in commands {
+            let mut app = fbx.app(args).await;
+
+            let result = run(&mut app).await;
+
+            assert!(matches!(result, Err(ClientError::PermissionDenied(_))));
+        }
+        assert!(fbx
+            .requests("DELETE", "/notif/targets/c5e4a9d1/")
+            .is_empty());
+        assert!(fbx.requests("PUT", "/notif/targets/c5e4a9d1/").is_empty());
+        assert!(fbx.requests("POST", "/notif/targets/").is_empty());
+    }
+}
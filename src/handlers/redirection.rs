@@ -0,0 +1,76 @@
+use crate::app::App;
+use crate::handlers::new_table;
+use crate::i18n::Message;
+use crate::models::args::ListArgs;
+use crate::models::exception::ClientError;
+use crate::output;
+use crate::services::api::FreeboxOSApiCalls;
+use comfy_table::Cell;
+
+pub struct Redirection;
+
+impl Redirection {
+    pub async fn list(app: &mut App, args: &ListArgs) -> Result<(), ClientError> {
+        let session_token = app.session_token().await?;
+        let redirections = app
+            .api
+            .port_forwardings(&app.client, session_token)
+            .await?
+            .result
+            .unwrap_or_default();
+        output::list(app.cli.output, args, &redirections, |redirections| {
+            let mut table = new_table(vec![
+                Message::RedirectionId.to_string(),
+                Message::RedirectionEnabled.to_string(),
+                Message::RedirectionProtocol.to_string(),
+                Message::RedirectionWanPorts.to_string(),
+                Message::RedirectionLanIp.to_string(),
+                Message::RedirectionLanPort.to_string(),
+                Message::RedirectionSource.to_string(),
+                Message::DhcpComment.to_string(),
+            ]);
+            for redirection in redirections {
+                let wan_ports = match redirection.wan_port_start == redirection.wan_port_end {
+                    true => redirection.wan_port_start.to_string(),
+                    false => format!(
+                        "{}-{}",
+                        redirection.wan_port_start, redirection.wan_port_end
+                    ),
+                };
+                table.add_row(vec![
+                    Cell::new(redirection.id),
+                    Cell::new(redirection.enabled),
+                    Cell::new(&redirection.ip_proto),
+                    Cell::new(wan_ports),
+                    Cell::new(&redirection.lan_ip),
+                    Cell::new(redirection.lan_port),
+                    Cell::new(&redirection.src_ip),
+                    Cell::new(&redirection.comment),
+                ]);
+            }
+            table
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fake::FakeFreebox;
+    use crate::output::captured;
+    use crate::run;
+    use serde_json::{json, Value};
+
+    #[tokio::test]
+    async fn list() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["--output", "yaml", "redirection", "list"]).await;
+
+        run(&mut app).await.unwrap();
+
+        assert_eq!(fbx.requests("GET", "/fw/redir/").len(), 1);
+        let redirections: Value = serde_yaml::from_str(&captured()).unwrap();
+        assert_eq!(redirections, FakeFreebox::fixture("fw_redir")["result"]);
+        assert_eq!(redirections[0]["lan_ip"], json!("192.168.1.2"));
+        assert_eq!(redirections[0]["wan_port_start"], json!(8443));
+    }
+}
@@ -1,26 +1,160 @@
 use crate::app::App;
+use crate::handlers::config::Config;
+use crate::handlers::new_table;
+use crate::i18n::Message;
+use crate::info;
+use crate::logging::Progress;
+use crate::models::args::SettingsDiscoverArgs;
 use crate::models::exception::ClientError;
+use crate::models::freebox::version::FreeboxMajorVersion;
+use crate::output;
+use crate::services::discover::{DeviceInfo, Discover, FreeboxDiscover, HttpProbeDiscover};
+use comfy_table::{Cell, Table};
+use inquire::Select;
+use std::time::Duration;
+use url::Url;
 
 pub struct Settings;
 
 impl Settings {
     pub async fn get_url(_app: &mut App) -> Result<(), ClientError> {
-        println!("Get settings");
+        info!("{}", Message::NotImplemented);
         Ok(())
     }
 
     pub async fn set_url(_app: &mut App) -> Result<(), ClientError> {
-        println!("Set settings");
+        info!("{}", Message::NotImplemented);
         Ok(())
     }
 
     pub async fn reset(_app: &mut App) -> Result<(), ClientError> {
-        println!("Reset settings");
+        info!("{}", Message::NotImplemented);
         Ok(())
     }
 
-    pub async fn discover(_app: &mut App) -> Result<(), ClientError> {
-        println!("Discover settings");
+    pub async fn discover(app: &mut App, args: &SettingsDiscoverArgs) -> Result<(), ClientError> {
+        let duration = Duration::from_secs(args.time);
+        let mut devices = vec![];
+        if !args.http {
+            let mut spinner = Progress::start(Message::DiscoverSearching);
+            devices = FreeboxDiscover
+                .discover_devices(duration)
+                .await
+                .unwrap_or_default();
+            spinner.stop();
+        }
+        if devices.is_empty() {
+            let mut spinner = Progress::start(Message::DiscoverProbing);
+            let probe = HttpProbeDiscover {
+                subnet: args.subnet,
+            };
+            let probed = probe.discover_devices(duration).await;
+            spinner.stop();
+            devices = probed?;
+        }
+        if devices.is_empty() {
+            info!("{}", Message::DiscoverNoneFound);
+            return Ok(());
+        }
+        output::list(app.cli.output, &args.list, &devices, Self::devices_table)?;
+        if args.no_select || !app.cli.output.is_human() {
+            return Ok(());
+        }
+        let options: Vec<String> = devices
+            .iter()
+            .map(|device| format!("{} - {}", device.box_model_name, device.url()))
+            .collect();
+        if let Ok(selected) =
+            Select::new(&Message::DiscoverSelect.to_string(), options).raw_prompt()
+        {
+            let device = &devices[selected.index];
+            let url = device.url();
+            let parsed = Url::parse(&url)
+                .map_err(|_| ClientError::InvalidUrl(Message::ConfigInvalidUrl.to_string()))?;
+            Config::switch_url(app, &parsed)?;
+            // Detected on first use otherwise.
+            if let Some(version) = FreeboxMajorVersion::from_api_version(&device.api_version) {
+                app.config.pref.version = version;
+                app.config.pref.api_base_url = Some(device.api_base_url.clone());
+            }
+            info!("{}", Message::ConfigUrl(&url));
+            info!("{}", Message::ConfigUrlUpdated);
+        }
         Ok(())
     }
+
+    fn devices_table(devices: &[DeviceInfo]) -> Table {
+        let mut table = new_table(vec![
+            Message::DiscoverModel.to_string(),
+            Message::DiscoverAddress.to_string(),
+            Message::DiscoverUid.to_string(),
+            Message::DiscoverApiVersion.to_string(),
+            Message::DiscoverApiDomain.to_string(),
+        ]);
+        for device in devices {
+            table.add_row(vec![
+                Cell::new(&device.box_model_name),
+                Cell::new(device.url()),
+                Cell::new(&device.uid),
+                Cell::new(&device.api_version),
+                Cell::new(&device.api_domain),
+            ]);
+        }
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Settings;
+    use crate::fake::FakeFreebox;
+    use crate::models::args::Cli;
+    use crate::output::captured;
+    use crate::run;
+    use crate::services::discover::DeviceInfo;
+    use clap::Parser;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    #[test]
+    fn devices_table_lists_the_model_and_the_url() {
+        let records = [
+            "uid=23b86ec8",
+            "api_version=10.2",
+            "box_model_name=Freebox v7",
+        ];
+        let address = Some(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 254)));
+        let device = DeviceInfo::from_txt_records(records.into_iter(), address).unwrap();
+
+        let table = Settings::devices_table(&[device]).to_string();
+
+        assert!(table.contains("Freebox v7"));
+        assert!(table.contains("http://192.168.1.254/"));
+        assert!(table.contains("23b86ec8"));
+        assert!(table.contains("10.2"));
+    }
+
+    #[tokio::test]
+    async fn discover_without_answers_keeps_the_url() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx
+            .app(&[
+                "--output", "json", "settings", "discover", "--http", "--time", "0",
+            ])
+            .await;
+
+        run(&mut app).await.unwrap();
+
+        assert_eq!(captured(), "");
+        assert_eq!(app.config.pref.url(), fbx.url);
+    }
+
+    #[test]
+    fn discover_rejects_large_subnets() {
+        for subnet in ["192.168.0.0/16", "192.168.1.0/24/1"] {
+            let args = ["fbx", "settings", "discover", "--subnet", subnet];
+            assert!(Cli::try_parse_from(args).is_err(), "{}", subnet);
+        }
+        let args = ["fbx", "settings", "discover", "--subnet", "192.168.1.0/24"];
+        assert!(Cli::try_parse_from(args).is_ok());
+    }
 }
@@ -1,21 +1,279 @@
 use crate::app::App;
+use crate::handlers::new_table;
+use crate::i18n::Message;
+use crate::info;
+use crate::models::args::{SystemHardwareArgs, SystemPowerArgs};
 use crate::models::exception::ClientError;
+use crate::models::freebox::authorization::Permission;
+use crate::models::freebox::system::{DiskStatus, SystemInfo, SystemSensor};
+use crate::output;
+use crate::services::api::FreeboxOSApiCalls;
+use comfy_table::{Cell, Color};
+use inquire::Confirm;
+use serde::Serialize;
+use std::fmt::Display;
 
 pub struct System;
 
+/// Health level of a hardware component, ordered from best to worst so the
+/// overall status is the maximum of every check (Nagios plugin convention).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HardwareHealth {
+    Ok,
+    Warning,
+    Critical,
+}
+
+impl HardwareHealth {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            HardwareHealth::Ok => 0,
+            HardwareHealth::Warning => 1,
+            HardwareHealth::Critical => 2,
+        }
+    }
+
+    fn cell(&self) -> Cell {
+        let color = match self {
+            HardwareHealth::Ok => Color::Green,
+            HardwareHealth::Warning => Color::Yellow,
+            HardwareHealth::Critical => Color::Red,
+        };
+        Cell::new(self).fg(color)
+    }
+
+    fn from_temperature(sensor: &SystemSensor, args: &SystemHardwareArgs) -> Self {
+        if sensor.value >= args.temp_crit {
+            HardwareHealth::Critical
+        } else if sensor.value >= args.temp_warn {
+            HardwareHealth::Warning
+        } else {
+            HardwareHealth::Ok
+        }
+    }
+
+    fn from_fan(fan: &SystemSensor, args: &SystemHardwareArgs) -> Self {
+        if fan.value <= 0 {
+            HardwareHealth::Critical
+        } else if fan.value < args.fan_min {
+            HardwareHealth::Warning
+        } else {
+            HardwareHealth::Ok
+        }
+    }
+
+    fn from_disk(status: &DiskStatus) -> Self {
+        match status {
+            DiskStatus::Error => HardwareHealth::Critical,
+            DiskStatus::Initializing => HardwareHealth::Warning,
+            _ => HardwareHealth::Ok,
+        }
+    }
+}
+
+impl Display for HardwareHealth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            HardwareHealth::Ok => "OK",
+            HardwareHealth::Warning => "WARN",
+            HardwareHealth::Critical => "CRIT",
+        };
+        write!(f, "{}", str)
+    }
+}
+
+/// Outcome of `fbx system hardware`, along with the raw system information.
+#[derive(Serialize)]
+struct HardwareReport {
+    status: String,
+    problems: Vec<String>,
+    system: SystemInfo,
+}
+
 impl System {
-    pub async fn shutdown(_app: &mut App) -> Result<(), ClientError> {
-        println!("Shutdown system");
+    pub async fn shutdown(app: &mut App, args: &SystemPowerArgs) -> Result<(), ClientError> {
+        let session_token = app.session_token_for(Permission::Settings).await?;
+        if !Self::confirm(args, Message::SystemShutdownConfirm) {
+            return Ok(());
+        }
+        app.api.system_shutdown(&app.client, session_token).await?;
+        info!("{}", Message::SystemShuttingDown);
         Ok(())
     }
 
-    pub async fn reboot(_app: &mut App) -> Result<(), ClientError> {
-        println!("Reboot system");
+    pub async fn reboot(app: &mut App, args: &SystemPowerArgs) -> Result<(), ClientError> {
+        let session_token = app.session_token_for(Permission::Settings).await?;
+        if !Self::confirm(args, Message::SystemRebootConfirm) {
+            return Ok(());
+        }
+        app.api.system_reboot(&app.client, session_token).await?;
+        info!("{}", Message::SystemRebooting);
         Ok(())
     }
 
+    fn confirm(args: &SystemPowerArgs, message: Message<'_>) -> bool {
+        args.yes
+            || Confirm::new(&message.to_string())
+                .with_default(false)
+                .prompt()
+                .unwrap_or(false)
+    }
+
     pub async fn get(_app: &mut App) -> Result<(), ClientError> {
-        println!("get system");
+        info!("{}", Message::NotImplemented);
         Ok(())
     }
+
+    pub async fn hardware(app: &mut App, args: &SystemHardwareArgs) -> Result<(), ClientError> {
+        let session_token = app.session_token().await?;
+        let info = app
+            .api
+            .system_info(&app.client, session_token)
+            .await?
+            .result;
+        let mut problems: Vec<String> = vec![];
+        let mut status = HardwareHealth::from_disk(&info.disk_status);
+        if status != HardwareHealth::Ok {
+            problems.push(Message::HardwareDiskProblem(&info.disk_status.to_string()).to_string());
+        }
+
+        let mut expansions = new_table(vec![
+            Message::HardwareSlot.to_string(),
+            Message::HardwareType.to_string(),
+            Message::HardwarePresent.to_string(),
+            Message::HardwareSupported.to_string(),
+            Message::HardwareStatus.to_string(),
+        ]);
+        for expansion in &info.expansions {
+            let health = if expansion.present && (!expansion.supported || !expansion.probe_done) {
+                problems.push(
+                    Message::HardwareExpansionProblem(expansion.slot, &expansion.kind).to_string(),
+                );
+                HardwareHealth::Warning
+            } else {
+                HardwareHealth::Ok
+            };
+            status = status.max(health);
+            expansions.add_row(vec![
+                Cell::new(expansion.slot),
+                Cell::new(&expansion.kind),
+                Cell::new(expansion.present),
+                Cell::new(expansion.supported),
+                health.cell(),
+            ]);
+        }
+
+        let mut sensors = new_table(vec![
+            Message::HardwareSensor.to_string(),
+            Message::HardwareValue.to_string(),
+            Message::HardwareStatus.to_string(),
+        ]);
+        for sensor in &info.sensors {
+            let health = HardwareHealth::from_temperature(sensor, args);
+            if health != HardwareHealth::Ok {
+                problems.push(format!("{} {}°C", sensor.name, sensor.value));
+            }
+            status = status.max(health);
+            sensors.add_row(vec![
+                Cell::new(&sensor.name),
+                Cell::new(format!("{} °C", sensor.value)),
+                health.cell(),
+            ]);
+        }
+        for fan in &info.fans {
+            let health = HardwareHealth::from_fan(fan, args);
+            if health != HardwareHealth::Ok {
+                problems.push(format!(
+                    "{} {}",
+                    fan.name,
+                    Message::HardwareFanSpeed(fan.value)
+                ));
+            }
+            status = status.max(health);
+            sensors.add_row(vec![
+                Cell::new(&fan.name),
+                Cell::new(Message::HardwareFanSpeed(fan.value)),
+                health.cell(),
+            ]);
+        }
+
+        let report = HardwareReport {
+            status: status.to_string(),
+            problems,
+            system: info,
+        };
+        output::item(app.cli.output, &report, |report| {
+            if report.problems.is_empty() {
+                println!(
+                    "HARDWARE {} - {}",
+                    report.status, report.system.model_info.pretty_name
+                );
+            } else {
+                println!(
+                    "HARDWARE {} - {}",
+                    report.status,
+                    report.problems.join(", ")
+                );
+            }
+            println!(
+                "{}",
+                Message::HardwareInternalDisk(&report.system.disk_status.to_string())
+            );
+            println!("{expansions}");
+            println!("{sensors}");
+        })?;
+        match status {
+            HardwareHealth::Ok => Ok(()),
+            _ => Err(ClientError::CheckFailed(status.exit_code())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fake::FakeFreebox;
+    use crate::models::exception::ClientError;
+    use crate::run;
+
+    #[tokio::test]
+    async fn hardware_is_ok() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["system", "hardware"]).await;
+
+        run(&mut app).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn hot_sensors_are_a_warning() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["system", "hardware", "--temp-warn", "50"]).await;
+
+        let result = run(&mut app).await;
+
+        assert_eq!(result, Err(ClientError::CheckFailed(1)));
+    }
+
+    #[tokio::test]
+    async fn reboot_and_shutdown() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["system", "reboot", "-y"]).await;
+        run(&mut app).await.unwrap();
+        let mut app = fbx.app(&["system", "shutdown", "-y"]).await;
+        run(&mut app).await.unwrap();
+
+        assert_eq!(fbx.requests("POST", "/system/reboot/").len(), 1);
+        assert_eq!(fbx.requests("POST", "/system/shutdown/").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn reboot_needs_the_settings_permission() {
+        let fbx = FakeFreebox::start();
+        fbx.deny("settings");
+        let mut app = fbx.app(&["system", "reboot", "-y"]).await;
+
+        let result = run(&mut app).await;
+
+        assert!(matches!(result, Err(ClientError::PermissionDenied(_))));
+        assert!(fbx.requests("POST", "/system/reboot/").is_empty());
+    }
 }
@@ -1,15 +1,77 @@
 use crate::app::App;
+use crate::handlers::new_table;
+use crate::i18n::Message;
 use crate::models::args::{VmGetArgs, VmListArgs};
 use crate::models::exception::ClientError;
+use crate::models::freebox::authorization::Permission;
+use crate::output;
+use crate::services::api::FreeboxOSApiCalls;
+use comfy_table::Cell;
 
 pub struct Vm;
 
 impl Vm {
-    pub async fn list(_app: &mut App, _arg: &VmListArgs) -> Result<(), ClientError> {
-        Ok(())
+    pub async fn list(app: &mut App, args: &VmListArgs) -> Result<(), ClientError> {
+        let session_token = app.session_token_for(Permission::Vm).await?;
+        let vms = app
+            .api
+            .vms(&app.client, session_token)
+            .await?
+            .result
+            .unwrap_or_default();
+        output::list(app.cli.output, &args.list, &vms, |vms| {
+            let mut table = new_table(vec![
+                Message::VmId.to_string(),
+                Message::VmName.to_string(),
+                Message::VmStatus.to_string(),
+                Message::VmMemory.to_string(),
+                Message::VmCpus.to_string(),
+                Message::VmOs.to_string(),
+            ]);
+            for vm in vms {
+                table.add_row(vec![
+                    Cell::new(vm.id),
+                    Cell::new(&vm.name),
+                    Cell::new(&vm.status),
+                    Cell::new(vm.memory),
+                    Cell::new(vm.vcpus),
+                    Cell::new(&vm.os),
+                ]);
+            }
+            table
+        })
     }
 
     pub async fn get(_app: &mut App, _arg: &VmGetArgs) -> Result<(), ClientError> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::fake::FakeFreebox;
+    use crate::models::exception::ClientError;
+    use crate::run;
+
+    #[tokio::test]
+    async fn list_needs_the_vm_permission() {
+        let fbx = FakeFreebox::start();
+        fbx.deny("vm");
+        let mut app = fbx.app(&["vm", "list"]).await;
+
+        let result = run(&mut app).await;
+
+        assert!(matches!(result, Err(ClientError::PermissionDenied(_))));
+        assert!(fbx.requests("GET", "/vm/").is_empty());
+    }
+
+    #[tokio::test]
+    async fn list() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["--output", "json", "vm", "list"]).await;
+
+        run(&mut app).await.unwrap();
+
+        assert_eq!(fbx.requests("GET", "/vm/").len(), 1);
+    }
+}
@@ -0,0 +1,418 @@
+use crate::app::App;
+use crate::handlers::new_table;
+use crate::i18n::Message;
+use crate::info;
+use crate::models::args::{ListArgs, WifiBssListArgs, WifiBssSetArgs};
+use crate::models::exception::ClientError;
+use crate::models::freebox::authorization::Permission;
+use crate::models::freebox::wifi::{
+    WifiAp, WifiBss, WifiBssConfigBody, WifiBssUpdateBody, WifiGlobalConfig, WifiGlobalConfigBody,
+};
+use crate::output;
+use crate::services::api::FreeboxOSApiCalls;
+use comfy_table::{Cell, Table};
+use serde::Serialize;
+
+const MASKED_KEY: &str = "********";
+
+pub struct Wifi;
+
+impl Wifi {
+    pub async fn status(app: &mut App) -> Result<(), ClientError> {
+        let session_token = app.session_token().await?;
+        let config = app
+            .api
+            .wifi_config(&app.client, session_token.clone())
+            .await?
+            .result;
+        let ap = app
+            .api
+            .wifi_aps(&app.client, session_token)
+            .await?
+            .result
+            .unwrap_or_default();
+        let status = WifiStatus { config, ap };
+        output::item(app.cli.output, &status, |status| {
+            println!("{}", Message::WifiEnabled(status.config.enabled));
+            println!(
+                "{}",
+                Message::WifiMacFilter(&status.config.mac_filter_state.to_string())
+            );
+            println!("{}", Self::aps_table(&status.ap));
+        })
+    }
+
+    pub async fn set_enabled(app: &mut App, enabled: bool) -> Result<(), ClientError> {
+        let session_token = app.session_token_for(Permission::Settings).await?;
+        let body = WifiGlobalConfigBody {
+            enabled: Some(enabled),
+        };
+        app.api
+            .update_wifi_config(&app.client, session_token, body)
+            .await?;
+        match enabled {
+            true => info!("{}", Message::WifiTurnedOn),
+            false => info!("{}", Message::WifiTurnedOff),
+        }
+        Ok(())
+    }
+
+    pub async fn aps(app: &mut App, args: &ListArgs) -> Result<(), ClientError> {
+        let session_token = app.session_token().await?;
+        let aps = app
+            .api
+            .wifi_aps(&app.client, session_token)
+            .await?
+            .result
+            .unwrap_or_default();
+        output::list(app.cli.output, args, &aps, Self::aps_table)
+    }
+
+    /// Lists the networks, their keys are masked unless asked for.
+    pub async fn bss(app: &mut App, args: &WifiBssListArgs) -> Result<(), ClientError> {
+        let session_token = app.session_token().await?;
+        let mut bss = app
+            .api
+            .wifi_bss(&app.client, session_token)
+            .await?
+            .result
+            .unwrap_or_default();
+        if !args.show_keys {
+            for bss in &mut bss {
+                bss.config.key = MASKED_KEY.to_string();
+            }
+        }
+        output::list(app.cli.output, &args.list, &bss, |bss| {
+            let mut table = new_table(vec![
+                Message::WifiBssId.to_string(),
+                Message::WifiBssAp.to_string(),
+                Message::WifiSsid.to_string(),
+                Message::WifiEncryption.to_string(),
+                Message::WifiKey.to_string(),
+                Message::WifiState.to_string(),
+                Message::WifiStations.to_string(),
+            ]);
+            for bss in bss {
+                table.add_row(vec![
+                    Cell::new(&bss.id),
+                    Cell::new(bss.phy_id),
+                    Cell::new(&bss.config.ssid),
+                    Cell::new(bss.config.encryption),
+                    Cell::new(&bss.config.key),
+                    Cell::new(&bss.status.state),
+                    Cell::new(bss.status.authorized_sta_count),
+                ]);
+            }
+            table
+        })
+    }
+
+    /// Changes the name, key or encryption of a network. The key is checked
+    /// against the encryption it will be used with before anything is sent.
+    pub async fn set_bss(app: &mut App, args: &WifiBssSetArgs) -> Result<(), ClientError> {
+        if args.ssid.is_none() && args.key.is_none() && args.encryption.is_none() {
+            return Err(ClientError::InvalidArgument(
+                Message::ErrorNothingToUpdate("--ssid, --key, --encryption").to_string(),
+            ));
+        }
+        let session_token = app.session_token_for(Permission::Settings).await?;
+        let bss = app
+            .api
+            .wifi_bss(&app.client, session_token.clone())
+            .await?
+            .result
+            .unwrap_or_default();
+        let target = bss
+            .iter()
+            .find(|bss| bss.id.eq_ignore_ascii_case(&args.id))
+            .ok_or(ClientError::NotFound(
+                Message::ErrorWifiBssNotFound(&args.id).to_string(),
+            ))?;
+        let encryption = args.encryption.unwrap_or(target.config.encryption);
+        let key = args.key.as_deref().unwrap_or(&target.config.key);
+        if !encryption.accepts_key(key) {
+            return Err(ClientError::InvalidArgument(
+                Message::ErrorWifiInvalidKey(&encryption.to_string()).to_string(),
+            ));
+        }
+        let body = WifiBssUpdateBody {
+            config: WifiBssConfigBody {
+                ssid: args.ssid.clone(),
+                encryption: args.encryption,
+                key: args.key.clone(),
+            },
+        };
+        let updated = app
+            .api
+            .update_wifi_bss(&app.client, session_token, &target.id, body)
+            .await?
+            .result;
+        info!("{}", Message::WifiBssUpdated(&updated.config.ssid));
+        if Self::shares_config(target, &bss) {
+            info!("{}", Message::WifiBssDefaultConfig);
+        }
+        Ok(())
+    }
+
+    /// Whether other networks follow the configuration of the main one.
+    fn shares_config(target: &WifiBss, bss: &[WifiBss]) -> bool {
+        target.status.is_main_bss
+            && bss
+                .iter()
+                .any(|other| other.id != target.id && other.config.use_default_config)
+    }
+
+    fn aps_table(aps: &[WifiAp]) -> Table {
+        let mut table = new_table(vec![
+            Message::WifiApId.to_string(),
+            Message::WifiApName.to_string(),
+            Message::WifiBand.to_string(),
+            Message::WifiState.to_string(),
+            Message::WifiChannel.to_string(),
+            Message::WifiChannelWidth.to_string(),
+        ]);
+        for ap in aps {
+            let channel = match ap.status.secondary_channel {
+                0 => ap.status.primary_channel.to_string(),
+                secondary => format!("{} + {}", ap.status.primary_channel, secondary),
+            };
+            table.add_row(vec![
+                Cell::new(ap.id),
+                Cell::new(&ap.name),
+                Cell::new(&ap.config.band),
+                Cell::new(&ap.status.state),
+                Cell::new(channel),
+                Cell::new(&ap.status.channel_width),
+            ]);
+        }
+        table
+    }
+}
+
+#[derive(Serialize)]
+struct WifiStatus {
+    #[serde(flatten)]
+    config: WifiGlobalConfig,
+    ap: Vec<WifiAp>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fake::FakeFreebox;
+    use crate::models::exception::ClientError;
+    use crate::output::captured;
+    use crate::run;
+    use serde_json::{json, Value};
+
+    const MAIN_BSS: &str = "/wifi/bss/F4:CA:E5:00:00:02/";
+
+    #[tokio::test]
+    async fn status_reads_the_config_and_the_aps() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["--output", "json", "wifi", "status"]).await;
+
+        run(&mut app).await.unwrap();
+
+        let status: Value = serde_json::from_str(&captured()).unwrap();
+        assert_eq!(status["enabled"], json!(true));
+        assert_eq!(status["mac_filter_state"], json!("disabled"));
+        let names: Vec<&Value> = status["ap"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|ap| &ap["name"])
+            .collect();
+        assert_eq!(names, vec!["2.4G", "5G"]);
+    }
+
+    #[tokio::test]
+    async fn off_disables_the_wifi() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["wifi", "off"]).await;
+
+        run(&mut app).await.unwrap();
+
+        let requests = fbx.requests("PUT", "/wifi/config/");
+        assert_eq!(requests[0].body, json!({"enabled": false}));
+    }
+
+    #[tokio::test]
+    async fn on_needs_the_settings_permission() {
+        let fbx = FakeFreebox::start();
+        fbx.deny("settings");
+        let mut app = fbx.app(&["wifi", "on"]).await;
+
+        let result = run(&mut app).await;
+
+        assert!(matches!(result, Err(ClientError::PermissionDenied(_))));
+        assert!(fbx.requests("PUT", "/wifi/config/").is_empty());
+    }
+
+    #[tokio::test]
+    async fn lists() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx
+            .app(&[
+                "wifi",
+                "ap",
+                "list",
+                "--output",
+                "csv",
+                "--columns",
+                "id,name,config.band",
+            ])
+            .await;
+        run(&mut app).await.unwrap();
+        assert_eq!(captured(), "id,name,config.band\n0,2.4G,2d4g\n1,5G,5g\n");
+
+        let mut app = fbx
+            .app(&[
+                "wifi",
+                "bss",
+                "list",
+                "--output",
+                "json",
+                "--filter",
+                "status.is_main_bss=true",
+            ])
+            .await;
+        run(&mut app).await.unwrap();
+        let bss: Value = serde_json::from_str(&captured()).unwrap();
+        assert_eq!(bss.as_array().unwrap().len(), 1);
+        assert_eq!(bss[0]["id"], json!("F4:CA:E5:00:00:02"));
+        assert_eq!(bss[0]["config"]["key"], json!("********"));
+    }
+
+    #[tokio::test]
+    async fn unknown_values_of_a_newer_firmware() {
+        let fbx = FakeFreebox::start();
+        let mut config = FakeFreebox::fixture("wifi_config");
+        config["result"]["mac_filter_state"] = json!("allowlist");
+        fbx.answer("GET", "/wifi/config/", 200, config);
+        let mut ap = FakeFreebox::fixture("wifi_ap");
+        ap["result"][0]["status"]["state"] = json!("zero_wait_dfs");
+        ap["result"][0]["config"]["band"] = json!("7g");
+        fbx.answer("GET", "/wifi/ap/", 200, ap);
+        let mut bss = FakeFreebox::fixture("wifi_bss");
+        bss["result"][0]["status"]["state"] = json!("sleeping");
+        bss["result"][0]["config"]["encryption"] = json!("wpa3_sae");
+        fbx.answer("GET", "/wifi/bss/", 200, bss);
+
+        let mut app = fbx.app(&["--output", "json", "wifi", "status"]).await;
+        run(&mut app).await.unwrap();
+        let status: Value = serde_json::from_str(&captured()).unwrap();
+        assert_eq!(status["mac_filter_state"], json!("unknown"));
+        assert_eq!(status["ap"][0]["status"]["state"], json!("unknown"));
+        assert_eq!(status["ap"][0]["config"]["band"], json!("unknown"));
+
+        let mut app = fbx.app(&["--output", "json", "wifi", "bss", "list"]).await;
+        run(&mut app).await.unwrap();
+        let bss: Value = serde_json::from_str(&captured()).unwrap();
+        assert_eq!(bss[0]["status"]["state"], json!("unknown"));
+        assert_eq!(bss[0]["config"]["encryption"], json!("unknown"));
+    }
+
+    #[tokio::test]
+    async fn set_rotates_the_key() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx
+            .app(&[
+                "wifi",
+                "bss",
+                "set",
+                "f4:ca:e5:00:00:02",
+                "-k",
+                "trois-petits-chats",
+            ])
+            .await;
+
+        run(&mut app).await.unwrap();
+
+        let requests = fbx.requests("PUT", MAIN_BSS);
+        assert_eq!(
+            requests[0].body,
+            json!({"config": {"key": "trois-petits-chats"}})
+        );
+    }
+
+    #[tokio::test]
+    async fn set_sends_only_the_given_fields() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx
+            .app(&[
+                "wifi",
+                "bss",
+                "set",
+                "F4:CA:E5:00:00:02",
+                "--ssid",
+                "Maison",
+                "--encryption",
+                "wpa23_psk_ccmp",
+            ])
+            .await;
+
+        run(&mut app).await.unwrap();
+
+        let requests = fbx.requests("PUT", MAIN_BSS);
+        assert_eq!(
+            requests[0].body,
+            json!({"config": {"ssid": "Maison", "encryption": "wpa23_psk_ccmp"}})
+        );
+    }
+
+    #[tokio::test]
+    async fn set_rejects_short_keys() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx
+            .app(&["wifi", "bss", "set", "F4:CA:E5:00:00:02", "-k", "1234"])
+            .await;
+
+        let result = run(&mut app).await;
+
+        assert!(matches!(result, Err(ClientError::InvalidArgument(_))));
+        assert!(fbx.requests("PUT", MAIN_BSS).is_empty());
+    }
+
+    #[tokio::test]
+    async fn set_checks_the_current_key_against_the_new_encryption() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx
+            .app(&["wifi", "bss", "set", "F4:CA:E5:00:00:02", "-e", "wep"])
+            .await;
+
+        let result = run(&mut app).await;
+
+        assert!(matches!(result, Err(ClientError::InvalidArgument(_))));
+        assert!(fbx.requests("PUT", MAIN_BSS).is_empty());
+    }
+
+    #[tokio::test]
+    async fn set_needs_a_change() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx.app(&["wifi", "bss", "set", "F4:CA:E5:00:00:02"]).await;
+
+        let result = run(&mut app).await;
+
+        assert!(matches!(result, Err(ClientError::InvalidArgument(_))));
+        assert!(fbx.requests("GET", "/wifi/bss/").is_empty());
+    }
+
+    #[tokio::test]
+    async fn set_unknown_bss_is_not_found() {
+        let fbx = FakeFreebox::start();
+        let mut app = fbx
+            .app(&[
+                "wifi",
+                "bss",
+                "set",
+                "00:00:00:00:00:00",
+                "--ssid",
+                "Maison",
+            ])
+            .await;
+
+        let result = run(&mut app).await;
+
+        assert!(matches!(result, Err(ClientError::NotFound(_))));
+    }
+}
@@ -0,0 +1,1108 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Locale {
+    #[default]
+    #[serde(rename = "fr")]
+    Fr,
+    #[serde(rename = "en")]
+    En,
+}
+
+impl Locale {
+    /// Reads the locale from the usual POSIX variables, `LC_ALL` taking
+    /// precedence over `LC_MESSAGES` and `LANG`.
+    pub fn from_env() -> Option<Self> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .map(|value| {
+                if value.to_lowercase().starts_with("fr") {
+                    Locale::Fr
+                } else {
+                    Locale::En
+                }
+            })
+    }
+}
+
+impl Display for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            Locale::Fr => "fr",
+            Locale::En => "en",
+        };
+        write!(f, "{}", str)
+    }
+}
+
+/// Sets the locale used by every [`Message`], must be called before the
+/// command line is parsed so that the help is translated too.
+pub fn init(locale: Locale) {
+    let _ = LOCALE.set(locale);
+}
+
+pub fn locale() -> Locale {
+    LOCALE.get().copied().unwrap_or_default()
+}
+
+/// Every user-facing string of the CLI.
+#[derive(Debug, Clone)]
+pub enum Message<'a> {
+    // Help
+    HelpAbout,
+    HelpTemplate,
+    HelpVerbose,
+    HelpQuiet,
+    HelpNoColor,
+    HelpOutput,
+    HelpListColumns,
+    HelpListFilter,
+    HelpListSortBy,
+    HelpListNoHeaders,
+    HelpDhcpStatic,
+    HelpConfigResetConfirm,
+    HelpConfigLocale,
+    HelpInitTime,
+    HelpAuthUrl,
+    HelpAuthResume,
+    HelpAuthWait,
+    HelpAuthForgetApp,
+    HelpAuthShowToken,
+    HelpSettingsNoSelect,
+    HelpSettingsBypassTest,
+    HelpHardwareTempWarn,
+    HelpHardwareTempCrit,
+    HelpHardwareFanMin,
+    HelpLcdBrightness,
+    HelpLcdOrientation,
+    HelpLcdHideWifiKey,
+    HelpAirMediaEnabled,
+    HelpAirMediaPassword,
+    HelpAirMediaPlayType,
+    HelpAirMediaStopType,
+    HelpAirMediaPosition,
+    HelpAirMediaReceiverPassword,
+    HelpLang,
+
+    HelpConfirmYes,
+    HelpNotificationTargetId,
+    HelpNotificationUnusedSince,
+    HelpNotificationSubscriptions,
+    HelpNotificationDeviceId,
+    HelpNotificationDeviceType,
+    HelpNotificationDeviceName,
+    HelpNotificationToken,
+    HelpNotificationBoxId,
+    HelpDiscoverSubnet,
+    HelpDiscoverHttp,
+    HelpHttpsCaFile,
+    HelpProfile,
+    HelpProfileName,
+    HelpProfileUrl,
+    HelpConfigPath,
+    HelpRequestTimeout,
+    HelpRetries,
+    HelpWifiShowKeys,
+    HelpWifiBssId,
+    HelpWifiSsid,
+    HelpWifiKey,
+    HelpWifiEncryption,
+    // Common
+    NotImplemented,
+
+    // Auth
+    AuthWaitingGrant,
+    AuthSuccess,
+    AuthFailed,
+    AuthLoginNotInitialized,
+    AuthSessionNotInitialized,
+    AuthSessionToken(&'a str),
+    AuthStatusUrl(&'a str),
+    AuthStatusApi(&'a str, &'a str),
+    AuthStatusApp(&'a str),
+    AuthStatusPending(i32),
+    AuthStatusGrantedAt(&'a str),
+    AuthStatusSessionAge(i64, i64),
+    AuthStatusLoggedIn(bool),
+    AuthStatusTokenValid,
+    AuthStatusTokenExpired,
+    AuthStatusBoxUnreachable,
+    AuthWaitingGrantOnBox(i32),
+    AuthDenied,
+    AuthBoxTimeout,
+    AuthUnknownRequest,
+    AuthStillPending,
+    AuthNothingToResume,
+    AuthLoggedOut,
+    AuthLogoutUnreachable,
+    AuthNoSession,
+    AuthAppForgotten(&'a str),
+    AuthRevokeHint,
+    AuthMissingPermission(&'a str),
+    AuthPermission,
+    AuthPermissionGranted,
+    AuthPermissionsUnknown,
+
+    // Config
+    ConfigUrl(&'a str),
+    ConfigUrlUpdated,
+    ConfigBoxChanged,
+    ConfigInvalidUrl,
+    ConfigResetConfirm,
+    ConfigResetting,
+    ConfigResetDone,
+    ConfigLocaleUpdated(Locale),
+
+    // System
+    HardwareInternalDisk(&'a str),
+    HardwareDiskProblem(&'a str),
+    HardwareExpansionProblem(i32, &'a str),
+    HardwareFanSpeed(i32),
+    HardwareSlot,
+    HardwareType,
+    HardwarePresent,
+    HardwareSupported,
+    HardwareStatus,
+    HardwareSensor,
+    HardwareValue,
+    SystemRebootConfirm,
+    SystemRebooting,
+    SystemShutdownConfirm,
+    SystemShuttingDown,
+
+    // LCD
+    LcdUpdated,
+    LcdBrightness(i32),
+    LcdOrientation(i32, bool),
+    LcdHideWifiKey(bool),
+
+    // AirMedia
+    AirMediaEnabled(bool),
+    AirMediaConfigUpdated,
+    AirMediaName,
+    AirMediaPassword,
+    AirMediaPhoto,
+    AirMediaAudio,
+    AirMediaVideo,
+    AirMediaScreen,
+    AirMediaPlaying(&'a str),
+    AirMediaStopped(&'a str),
+    AirMediaPasswordPrompt(&'a str),
+
+    // Lang
+    LangCurrent(&'a str),
+    LangAvailable(&'a str),
+    LangAlreadySet(&'a str),
+    LangUpdated(&'a str),
+
+    // Notification
+    NotificationId,
+    NotificationName,
+    NotificationType,
+    NotificationLastUse,
+    NotificationSubscriptions,
+    NotificationNever,
+    NotificationNoTarget,
+    NotificationRemoveConfirm(usize),
+    NotificationRemoved(&'a str),
+    NotificationSubscriptionsUpdated(&'a str),
+    NotificationRegistered(&'a str),
+
+    // Discover
+    DiscoverSearching,
+    DiscoverNoneFound,
+    DiscoverSelect,
+    DiscoverModel,
+    DiscoverAddress,
+    DiscoverUid,
+    DiscoverApiVersion,
+    DiscoverApiDomain,
+
+    // Discover (HTTP)
+    DiscoverInvalidSubnet(u8),
+    DiscoverProbing,
+
+    // HTTPS
+    HttpsEnabled(&'a str),
+    HttpsDisabled,
+    HttpsUnavailable,
+    HttpsCaFileRequired,
+
+    // Remote access
+    RemoteAccess(bool),
+    RemoteApiAccess(bool),
+    RemoteIp(&'a str),
+    RemotePort(i32),
+    RemoteEndpoint(&'a str),
+    RemoteApiEnabled(&'a str),
+    RemoteApiDisabled,
+    RemoteNotConfigured,
+    RemoteModeEnabled(&'a str),
+    RemoteModeDisabled,
+
+    // Profiles
+    ProfileName,
+    ProfileUrl,
+    ProfileApp,
+    ProfileCurrent,
+    ProfileAppGranted,
+    ProfileAppPending,
+    ProfileAppNone,
+    ProfileAdded(&'a str),
+    ProfileExists(&'a str),
+    ProfileUnknown(&'a str),
+    ProfileUsed(&'a str),
+    ProfileRemoveConfirm(&'a str),
+    ProfileRemoved(&'a str),
+    ProfileInUse(&'a str),
+
+    // Secrets
+    SecretPassphrase,
+    SecretStoreFailed(&'a str),
+
+    // Output
+    OutputField,
+    OutputValue,
+    ListUnknownField(&'a str, &'a str),
+
+    // Devices
+    DeviceName,
+    DeviceType,
+    DeviceIp,
+    DeviceMac,
+    DeviceVendor,
+    DeviceActive,
+
+    // DHCP
+    DhcpHostname,
+    DhcpStatic,
+    DhcpRemaining,
+    DhcpComment,
+
+    // Redirections
+    RedirectionEnabled,
+    RedirectionId,
+    RedirectionProtocol,
+    RedirectionWanPorts,
+    RedirectionLanIp,
+    RedirectionLanPort,
+    RedirectionSource,
+
+    // VM
+    VmName,
+    VmId,
+    VmStatus,
+    VmMemory,
+    VmCpus,
+    VmOs,
+
+    // Wi-Fi
+    WifiEnabled(bool),
+    WifiMacFilter(&'a str),
+    WifiTurnedOn,
+    WifiTurnedOff,
+    WifiApId,
+    WifiApName,
+    WifiBand,
+    WifiState,
+    WifiChannel,
+    WifiChannelWidth,
+    WifiBssId,
+    WifiBssAp,
+    WifiSsid,
+    WifiEncryption,
+    WifiKey,
+    WifiStations,
+    WifiBssUpdated(&'a str),
+    WifiBssDefaultConfig,
+
+    // Errors
+    ErrorFailure,
+    ErrorInvalidInput,
+    ErrorAuthRequired,
+    ErrorPermissionDenied,
+    ErrorNotFound,
+    ErrorTimeout,
+    ErrorUnreachable,
+    ErrorBox,
+    ErrorDetail(&'a str, &'a str),
+
+    // Error details
+    ErrorUnknownField(&'a str),
+    ErrorInvalidFilter(&'a str),
+    ErrorNothingToUpdate(&'a str),
+    ErrorMissingChallenge,
+    ErrorSignChallenge,
+    ErrorCaFileNotFound(&'a str),
+    ErrorInvalidCaFile,
+    ErrorUnexpectedHttpsBox,
+    ErrorInvalidApiVersion(&'a str),
+    ErrorInvalidUnusedSince(i64),
+    ErrorMdnsUnavailable,
+    ErrorNotificationTargetNotFound(&'a str),
+    ErrorNotificationTargetRequired,
+    ErrorWifiBssNotFound(&'a str),
+    ErrorWifiInvalidKey(&'a str),
+    ErrorAirMediaReceiverNotFound(&'a str),
+    ErrorAirMediaUnsupportedMedia,
+    ErrorAirMediaPasswordRequired,
+    ErrorLangUnavailable(&'a str),
+    ErrorLcdOrientation,
+    ErrorLcdOrientationForced,
+    ErrorResourceNotFound,
+    ErrorBadRequest,
+    ErrorBoxInternal,
+    ErrorParseBody,
+    ErrorRequestFailed,
+    ErrorSecretStoreUnavailable,
+    ErrorInvalidPassphrase,
+    ErrorSecretStoreCorrupted,
+    ErrorConfigWrite(&'a str, &'a str),
+    ErrorConfirmationRequired,
+}
+
+impl Message<'_> {
+    fn fr(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Message::HelpAbout => write!(f, "Une CLI pour gérer les commandes fbx"),
+            Message::HelpTemplate => write!(
+                f,
+                "\
+{{bin}} {{version}}
+{{author}}
+{{about}}
+
+\x1b[1mUSAGE:\x1b[0m
+    {{usage}}
+
+\x1b[1mCOMMANDS:\x1b[0m
+{{subcommands}}
+
+\x1b[1mOPTIONS:\x1b[0m
+{{options}}
+
+Pour plus d'informations, utilisez '{{bin}} <commande> --help'"
+            ),
+            Message::HelpVerbose => write!(f, "Affiche les requêtes envoyées sur la sortie d'erreur (-vv : avec leur contenu)"),
+            Message::HelpQuiet => write!(f, "N'affiche que les résultats"),
+            Message::HelpNoColor => write!(f, "Désactive les couleurs (aussi avec NO_COLOR)"),
+            Message::HelpOutput => write!(f, "Format de sortie des résultats"),
+            Message::HelpListColumns => write!(f, "Colonnes à afficher, séparées par des virgules (noms des champs de l'API)"),
+            Message::HelpListFilter => write!(f, "Garde les lignes où champ=valeur (ou champ!=valeur), répétable"),
+            Message::HelpListSortBy => write!(f, "Trie les lignes selon ce champ"),
+            Message::HelpListNoHeaders => write!(f, "N'affiche pas les en-têtes"),
+            Message::HelpDhcpStatic => write!(f, "Liste les baux statiques au lieu des baux dynamiques"),
+            Message::HelpConfigResetConfirm => {
+                write!(f, "Réinitialise les paramètres de l'application")
+            }
+            Message::HelpConfigLocale => write!(f, "Langue des messages de la CLI"),
+            Message::HelpInitTime => {
+                write!(f, "Délai d'attente pour la découverte des périphériques")
+            }
+            Message::HelpAuthUrl => write!(f, "Url de la Freebox"),
+            Message::HelpAuthResume => write!(f, "Reprend une demande d'autorisation en attente"),
+            Message::HelpAuthWait => write!(f, "Durée maximale d'attente de l'autorisation, en secondes"),
+            Message::HelpAuthForgetApp => write!(f, "Supprime aussi le token de l'application"),
+            Message::HelpAuthShowToken => write!(f, "Affiche le jeton de session en clair"),
+            Message::HelpSettingsNoSelect => write!(f, "Affiche les Freebox sans en choisir une"),
+            Message::HelpSettingsBypassTest => write!(f, "Ne teste pas l'url fournie"),
+            Message::HelpHardwareTempWarn => write!(
+                f,
+                "Température (°C) à partir de laquelle un capteur est en alerte"
+            ),
+            Message::HelpHardwareTempCrit => write!(
+                f,
+                "Température (°C) à partir de laquelle un capteur est critique"
+            ),
+            Message::HelpHardwareFanMin => write!(
+                f,
+                "Vitesse minimale (tr/min) d'un ventilateur en fonctionnement"
+            ),
+            Message::HelpLcdBrightness => write!(f, "Luminosité de l'écran (0-100)"),
+            Message::HelpLcdOrientation => {
+                write!(f, "Orientation de l'écran (0, 90, 180 ou 270)")
+            }
+            Message::HelpLcdHideWifiKey => write!(f, "Masque la clé Wi-Fi sur l'écran"),
+            Message::HelpAirMediaEnabled => write!(f, "Active ou désactive AirMedia"),
+            Message::HelpAirMediaPassword => write!(f, "Mot de passe AirMedia de la Freebox"),
+            Message::HelpAirMediaPlayType => write!(f, "Type de média à diffuser"),
+            Message::HelpAirMediaStopType => write!(f, "Type de média à arrêter"),
+            Message::HelpAirMediaPosition => write!(f, "Position de départ (en secondes)"),
+            Message::HelpAirMediaReceiverPassword => write!(f, "Mot de passe du récepteur"),
+            Message::HelpLang => {
+                write!(f, "Langue de l'interface de la Freebox (ex: fra, eng)")
+            }
+            Message::HelpConfirmYes => write!(f, "Ne demande pas de confirmation"),
+            Message::HelpNotificationTargetId => write!(f, "Identifiant de la cible"),
+            Message::HelpNotificationUnusedSince => {
+                write!(f, "Supprime les cibles inutilisées depuis N jours")
+            }
+            Message::HelpNotificationSubscriptions => write!(
+                f,
+                "Abonnements séparés par des virgules (ex: security,phone)"
+            ),
+            Message::HelpNotificationDeviceId => write!(f, "Identifiant unique de l'appareil"),
+            Message::HelpNotificationDeviceType => write!(f, "Type d'appareil (ex: android, ios)"),
+            Message::HelpNotificationDeviceName => write!(f, "Nom de l'appareil"),
+            Message::HelpNotificationToken => write!(f, "Jeton de notification push de l'appareil"),
+            Message::HelpNotificationBoxId => {
+                write!(f, "Identifiant de la Freebox (uid), détecté si absent")
+            }
+            Message::HelpDiscoverSubnet => write!(
+                f,
+                "Sous-réseau à sonder en HTTP si mDNS échoue (ex: 192.168.1.0/24)"
+            ),
+            Message::HelpDiscoverHttp => {
+                write!(f, "Sonde directement en HTTP sans passer par mDNS")
+            }
+            Message::HelpHttpsCaFile => {
+                write!(f, "Fichier PEM d'autorités racines remplaçant celles de la Freebox, embarquées dans fbx")
+            }
+            Message::HelpProfile => write!(f, "Profil de la Freebox à utiliser"),
+            Message::HelpProfileName => write!(f, "Nom du profil"),
+            Message::HelpProfileUrl => write!(f, "Url de la Freebox du profil"),
+            Message::HelpConfigPath => write!(f, "Fichier de configuration à utiliser"),
+            Message::HelpRequestTimeout => write!(f, "Durée maximale d'une requête à la Freebox, en secondes"),
+            Message::HelpRetries => write!(f, "Nombre de nouvelles tentatives des requêtes GET, PUT et DELETE en échec (jamais des POST)"),
+            Message::HelpWifiShowKeys => write!(f, "Affiche les clés Wi-Fi en clair"),
+            Message::HelpWifiBssId => write!(f, "Identifiant du réseau (BSSID)"),
+            Message::HelpWifiSsid => write!(f, "Nom du réseau (SSID)"),
+            Message::HelpWifiKey => write!(f, "Clé du réseau"),
+            Message::HelpWifiEncryption => write!(f, "Chiffrement du réseau"),
+
+            Message::NotImplemented => write!(f, "Commande pas encore disponible"),
+
+            Message::AuthWaitingGrant => write!(
+                f,
+                "En attente de l'acceptation de l'application sur la Freebox..."
+            ),
+            Message::AuthSuccess => write!(f, "Authentification réussie !"),
+            Message::AuthFailed => write!(f, "Authentification échouée !"),
+            Message::AuthLoginNotInitialized => write!(f, "Connexion non initialisée"),
+            Message::AuthSessionNotInitialized => write!(f, "Session non initialisée"),
+            Message::AuthSessionToken(token) => write!(f, "Jeton de session : {}", token),
+            Message::AuthStatusUrl(url) => write!(f, "URL de la Freebox : {}", url),
+            Message::AuthStatusApi(version, path) => write!(f, "Version de l'API : {} ({})", version, path),
+            Message::AuthStatusApp(app_id) => write!(f, "Application : {}", app_id),
+            Message::AuthStatusPending(track_id) => write!(f, "Autorisation {} en attente, reprenez-la avec `fbx auth login --resume`", track_id),
+            Message::AuthStatusGrantedAt(date) => write!(f, "Autorisée le : {}", date),
+            Message::AuthStatusSessionAge(hours, minutes) => write!(f, "Session ouverte depuis : {} h {:02} min", hours, minutes),
+            Message::AuthStatusLoggedIn(logged_in) => write!(f, "Connectée (logged_in) : {}", logged_in),
+            Message::AuthStatusTokenValid => write!(f, "Jeton de session valide"),
+            Message::AuthStatusTokenExpired => write!(f, "Jeton de session expiré, une nouvelle session sera ouverte à la prochaine commande"),
+            Message::AuthStatusBoxUnreachable => write!(f, "Freebox injoignable, validité du jeton inconnue"),
+            Message::AuthWaitingGrantOnBox(track_id) => write!(f, "Validez la demande n°{} sur l'écran de la Freebox...", track_id),
+            Message::AuthDenied => write!(f, "L'application a été refusée sur la Freebox !"),
+            Message::AuthBoxTimeout => write!(f, "La demande a expiré sur la Freebox, relancez `fbx auth login` !"),
+            Message::AuthUnknownRequest => write!(f, "La Freebox ne connaît pas cette demande, relancez `fbx auth login` !"),
+            Message::AuthStillPending => write!(f, "Toujours en attente, reprenez plus tard avec `fbx auth login --resume`."),
+            Message::AuthNothingToResume => write!(f, "Aucune demande d'autorisation en attente !"),
+            Message::AuthLoggedOut => write!(f, "Session fermée !"),
+            Message::AuthLogoutUnreachable => write!(f, "La Freebox est injoignable, la session a été oubliée et expirera d'elle-même."),
+            Message::AuthNoSession => write!(f, "Aucune session ouverte."),
+            Message::AuthAppForgotten(app_id) => write!(f, "Le token de l'application {} a été supprimé.", app_id),
+            Message::AuthRevokeHint => write!(f, "Pour révoquer l'application, ouvrez Freebox OS > Paramètres de la Freebox > Gestion des accès > Applications et supprimez-la."),
+            Message::AuthMissingPermission(permission) => write!(f, "L'application n'a pas la permission `{}`, accordez-la dans Freebox OS > Paramètres de la Freebox > Gestion des accès > Applications.", permission),
+            Message::AuthPermission => write!(f, "Permission"),
+            Message::AuthPermissionGranted => write!(f, "Accordée"),
+            Message::AuthPermissionsUnknown => write!(f, "Permissions inconnues, elles seront connues à la prochaine ouverture de session."),
+
+            Message::ConfigUrl(url) => write!(f, "Url : {}", url),
+            Message::ConfigUrlUpdated => write!(f, "Url mise à jour !"),
+            Message::ConfigBoxChanged => write!(f, "Nouvelle Freebox, la session et le jeton de l'application précédente sont oubliés."),
+            Message::ConfigInvalidUrl => write!(f, "Url non valide !"),
+            Message::ConfigResetConfirm => write!(
+                f,
+                "Êtes-vous sûr de vouloir réinitialiser la configuration ?"
+            ),
+            Message::ConfigResetting => write!(f, "Réinitialisation de la configuration..."),
+            Message::ConfigResetDone => write!(f, "Configuration réinitialisée !"),
+            Message::ConfigLocaleUpdated(locale) => {
+                write!(f, "Langue de la CLI mise à jour : {} !", locale)
+            }
+
+            Message::HardwareInternalDisk(status) => write!(f, "Disque interne : {}", status),
+            Message::HardwareDiskProblem(status) => write!(f, "disque interne {}", status),
+            Message::HardwareExpansionProblem(slot, kind) => {
+                write!(f, "extension {} ({})", slot, kind)
+            }
+            Message::HardwareFanSpeed(speed) => write!(f, "{} tr/min", speed),
+            Message::HardwareSlot => write!(f, "Slot"),
+            Message::HardwareType => write!(f, "Type"),
+            Message::HardwarePresent => write!(f, "Présent"),
+            Message::HardwareSupported => write!(f, "Supporté"),
+            Message::HardwareStatus => write!(f, "État"),
+            Message::HardwareSensor => write!(f, "Capteur"),
+            Message::HardwareValue => write!(f, "Valeur"),
+            Message::SystemRebootConfirm => write!(f, "Redémarrer la Freebox ? La connexion sera coupée quelques minutes."),
+            Message::SystemRebooting => write!(f, "Redémarrage de la Freebox en cours..."),
+            Message::SystemShutdownConfirm => write!(f, "Éteindre la Freebox ? Elle ne pourra être rallumée que sur place."),
+            Message::SystemShuttingDown => write!(f, "Extinction de la Freebox en cours..."),
+
+            Message::LcdUpdated => write!(f, "Écran mis à jour !"),
+            Message::LcdBrightness(brightness) => write!(f, "Luminosité : {}%", brightness),
+            Message::LcdOrientation(orientation, forced) => write!(
+                f,
+                "Orientation : {}°{}",
+                orientation,
+                if *forced { " (imposée)" } else { "" }
+            ),
+            Message::LcdHideWifiKey(hidden) => write!(f, "Clé Wi-Fi masquée : {}", hidden),
+
+            Message::AirMediaEnabled(enabled) => write!(f, "Activé : {}", enabled),
+            Message::AirMediaConfigUpdated => write!(f, "Configuration AirMedia mise à jour !"),
+            Message::AirMediaName => write!(f, "Nom"),
+            Message::AirMediaPassword => write!(f, "Mot de passe"),
+            Message::AirMediaPhoto => write!(f, "Photo"),
+            Message::AirMediaAudio => write!(f, "Audio"),
+            Message::AirMediaVideo => write!(f, "Vidéo"),
+            Message::AirMediaScreen => write!(f, "Écran"),
+            Message::AirMediaPlaying(receiver) => write!(f, "Lecture lancée sur {} !", receiver),
+            Message::AirMediaStopped(receiver) => {
+                write!(f, "Lecture arrêtée sur {} !", receiver)
+            }
+            Message::AirMediaPasswordPrompt(receiver) => {
+                write!(f, "Mot de passe de {} :", receiver)
+            }
+
+            Message::LangCurrent(lang) => write!(f, "Langue : {}", lang),
+            Message::LangAvailable(langs) => write!(f, "Disponibles : {}", langs),
+            Message::LangAlreadySet(lang) => write!(f, "La langue est déjà {} !", lang),
+            Message::LangUpdated(lang) => write!(f, "Langue mise à jour : {} !", lang),
+
+            Message::NotificationId => write!(f, "Id"),
+            Message::NotificationName => write!(f, "Nom"),
+            Message::NotificationType => write!(f, "Type"),
+            Message::NotificationLastUse => write!(f, "Dernière utilisation"),
+            Message::NotificationSubscriptions => write!(f, "Abonnements"),
+            Message::NotificationNever => write!(f, "jamais"),
+            Message::NotificationNoTarget => write!(f, "Aucune cible à supprimer"),
+            Message::NotificationRemoveConfirm(count) => {
+                write!(f, "Supprimer {} cible(s) de notification ?", count)
+            }
+            Message::NotificationRemoved(name) => write!(f, "Cible {} supprimée !", name),
+            Message::NotificationSubscriptionsUpdated(name) => {
+                write!(f, "Abonnements de {} mis à jour !", name)
+            }
+            Message::NotificationRegistered(id) => write!(f, "Cible {} enregistrée !", id),
+
+            Message::DiscoverSearching => write!(f, "Recherche des Freebox sur le réseau..."),
+            Message::DiscoverNoneFound => write!(f, "Aucune Freebox trouvée"),
+            Message::DiscoverSelect => write!(f, "Quelle Freebox utiliser ?"),
+            Message::DiscoverModel => write!(f, "Modèle"),
+            Message::DiscoverAddress => write!(f, "Adresse"),
+            Message::DiscoverUid => write!(f, "Uid"),
+            Message::DiscoverApiVersion => write!(f, "Version API"),
+            Message::DiscoverApiDomain => write!(f, "Domaine API"),
+
+            Message::DiscoverInvalidSubnet(prefix) => write!(
+                f,
+                "Sous-réseau invalide, attendu a.b.c.d/n avec n >= {} (ex: 192.168.1.0/24)",
+                prefix
+            ),
+            Message::DiscoverProbing => {
+                write!(f, "Recherche des Freebox en HTTP...")
+            }
+
+            Message::HttpsEnabled(url) => {
+                write!(f, "HTTPS activé, la Freebox est joignable sur {} !", url)
+            }
+            Message::HttpsDisabled => write!(f, "HTTPS désactivé !"),
+            Message::HttpsUnavailable => write!(f, "Cette Freebox ne propose pas l'accès HTTPS !"),
+            Message::HttpsCaFileRequired => write!(
+                f,
+                "Aucune autorité racine Freebox n'est embarquée, précisez un fichier PEM avec --ca-file !"
+            ),
+
+            Message::RemoteAccess(enabled) => write!(f, "Accès distant : {}", enabled),
+            Message::RemoteApiAccess(enabled) => write!(f, "Accès distant à l'API : {}", enabled),
+            Message::RemoteIp(ip) => write!(f, "IP publique : {}", ip),
+            Message::RemotePort(port) => write!(f, "Port HTTPS distant : {}", port),
+            Message::RemoteEndpoint(url) => write!(f, "Adresse distante enregistrée : {}", url),
+            Message::RemoteApiEnabled(url) => write!(f, "Accès distant à l'API activé, la Freebox est joignable sur {} !", url),
+            Message::RemoteApiDisabled => write!(f, "Accès distant à l'API désactivé !"),
+            Message::RemoteNotConfigured => write!(f, "Aucune adresse distante, lancez `fbx connection remote-api enable` depuis le réseau local !"),
+            Message::RemoteModeEnabled(url) => write!(f, "Mode distant activé, la Freebox sera contactée sur {} !", url),
+            Message::RemoteModeDisabled => write!(f, "Mode distant désactivé !"),
+
+            Message::ProfileName => write!(f, "Profil"),
+            Message::ProfileUrl => write!(f, "Url"),
+            Message::ProfileApp => write!(f, "Application"),
+            Message::ProfileCurrent => write!(f, "Par défaut"),
+            Message::ProfileAppGranted => write!(f, "autorisée"),
+            Message::ProfileAppPending => write!(f, "en attente"),
+            Message::ProfileAppNone => write!(f, "aucune"),
+            Message::ProfileAdded(name) => write!(f, "Profil {} ajouté !", name),
+            Message::ProfileExists(name) => write!(f, "Le profil {} existe déjà !", name),
+            Message::ProfileUnknown(name) => write!(f, "Profil {} inconnu !", name),
+            Message::ProfileUsed(name) => write!(f, "Le profil {} est désormais utilisé par défaut !", name),
+            Message::ProfileRemoveConfirm(name) => write!(f, "Supprimer le profil {} et son application ?", name),
+            Message::ProfileRemoved(name) => write!(f, "Profil {} supprimé !", name),
+            Message::ProfileInUse(name) => write!(f, "Le profil {} est utilisé, choisissez-en un autre avant de le supprimer !", name),
+
+            Message::SecretPassphrase => write!(f, "Phrase de passe du coffre de secrets :"),
+            Message::SecretStoreFailed(error) => write!(f, "Le token de l'application n'a pas pu être mis dans le coffre de secrets, la configuration n'est pas enregistrée ({})", error),
+
+            Message::OutputField => write!(f, "Champ"),
+            Message::OutputValue => write!(f, "Valeur"),
+            Message::ListUnknownField(field, fields) => write!(f, "Champ inconnu : {}, champs disponibles : {}", field, fields),
+
+            Message::DeviceName => write!(f, "Nom"),
+            Message::DeviceType => write!(f, "Type"),
+            Message::DeviceIp => write!(f, "IP"),
+            Message::DeviceMac => write!(f, "MAC"),
+            Message::DeviceVendor => write!(f, "Fabricant"),
+            Message::DeviceActive => write!(f, "Actif"),
+
+            Message::DhcpHostname => write!(f, "Nom d'hôte"),
+            Message::DhcpStatic => write!(f, "Statique"),
+            Message::DhcpRemaining => write!(f, "Bail restant"),
+            Message::DhcpComment => write!(f, "Commentaire"),
+
+            Message::RedirectionEnabled => write!(f, "Active"),
+            Message::RedirectionId => write!(f, "Id"),
+            Message::RedirectionProtocol => write!(f, "Protocole"),
+            Message::RedirectionWanPorts => write!(f, "Ports WAN"),
+            Message::RedirectionLanIp => write!(f, "IP LAN"),
+            Message::RedirectionLanPort => write!(f, "Port LAN"),
+            Message::RedirectionSource => write!(f, "IP source"),
+
+            Message::VmName => write!(f, "Nom"),
+            Message::VmId => write!(f, "Id"),
+            Message::VmStatus => write!(f, "État"),
+            Message::VmMemory => write!(f, "Mémoire (Mo)"),
+            Message::VmCpus => write!(f, "vCPU"),
+            Message::VmOs => write!(f, "Système"),
+
+            Message::WifiEnabled(enabled) => write!(f, "Wi-Fi activé : {}", enabled),
+            Message::WifiMacFilter(state) => write!(f, "Filtrage MAC : {}", state),
+            Message::WifiTurnedOn => write!(f, "Wi-Fi activé !"),
+            Message::WifiTurnedOff => write!(f, "Wi-Fi désactivé !"),
+            Message::WifiApId => write!(f, "Id"),
+            Message::WifiApName => write!(f, "Nom"),
+            Message::WifiBand => write!(f, "Bande"),
+            Message::WifiState => write!(f, "État"),
+            Message::WifiChannel => write!(f, "Canal"),
+            Message::WifiChannelWidth => write!(f, "Largeur (MHz)"),
+            Message::WifiBssId => write!(f, "BSSID"),
+            Message::WifiBssAp => write!(f, "Point d'accès"),
+            Message::WifiSsid => write!(f, "SSID"),
+            Message::WifiEncryption => write!(f, "Chiffrement"),
+            Message::WifiKey => write!(f, "Clé"),
+            Message::WifiStations => write!(f, "Clients"),
+            Message::WifiBssUpdated(ssid) => write!(f, "Réseau {} mis à jour !", ssid),
+            Message::WifiBssDefaultConfig => write!(f, "Les réseaux utilisant la configuration par défaut sont aussi modifiés."),
+
+            Message::ErrorFailure => write!(f, "Erreur inattendue"),
+            Message::ErrorInvalidInput => write!(f, "Paramètre invalide"),
+            Message::ErrorAuthRequired => write!(f, "Authentification requise, lancez `fbx auth login`"),
+            Message::ErrorPermissionDenied => write!(f, "Permission refusée"),
+            Message::ErrorNotFound => write!(f, "Introuvable"),
+            Message::ErrorTimeout => write!(f, "La Freebox n'a pas répondu à temps"),
+            Message::ErrorUnreachable => write!(f, "Freebox injoignable, vérifiez l'url avec `fbx config info`"),
+            Message::ErrorBox => write!(f, "La Freebox a refusé la requête"),
+            Message::ErrorDetail(error, detail) => write!(f, "{} : {}", error, detail),
+
+            Message::ErrorUnknownField(field) => write!(f, "Champ `{}` inconnu !", field),
+            Message::ErrorInvalidFilter(filter) => write!(f, "Filtre `{}` invalide, attendu champ=valeur ou champ!=valeur !", filter),
+            Message::ErrorNothingToUpdate(options) => write!(f, "Aucun paramètre à modifier ({}) !", options),
+            Message::ErrorMissingChallenge => write!(f, "Challenge manquant !"),
+            Message::ErrorSignChallenge => write!(f, "Impossible de signer le challenge avec le jeton d'application !"),
+            Message::ErrorCaFileNotFound(path) => write!(f, "Certificat racine {} introuvable !", path),
+            Message::ErrorInvalidCaFile => write!(f, "Certificat racine invalide !"),
+            Message::ErrorUnexpectedHttpsBox => write!(f, "Freebox HTTPS inattendue !"),
+            Message::ErrorInvalidApiVersion(version) => write!(f, "Version d'API {} invalide !", version),
+            Message::ErrorInvalidUnusedSince(days) => write!(f, "Durée de {} jours invalide !", days),
+            Message::ErrorMdnsUnavailable => write!(f, "mDNS indisponible !"),
+            Message::ErrorNotificationTargetNotFound(id) => write!(f, "Cible de notification {} introuvable !", id),
+            Message::ErrorNotificationTargetRequired => write!(f, "Précisez un identifiant ou --unused-since !"),
+            Message::ErrorWifiBssNotFound(id) => write!(f, "Réseau Wi-Fi {} introuvable !", id),
+            Message::ErrorWifiInvalidKey(encryption) => write!(f, "Clé Wi-Fi invalide pour le chiffrement {} !", encryption),
+            Message::ErrorAirMediaReceiverNotFound(name) => write!(f, "Récepteur AirMedia {} introuvable !", name),
+            Message::ErrorAirMediaUnsupportedMedia => write!(f, "Ce récepteur ne prend pas en charge ce type de média !"),
+            Message::ErrorAirMediaPasswordRequired => write!(f, "Mot de passe du récepteur requis !"),
+            Message::ErrorLangUnavailable(lang) => write!(f, "Langue {} non disponible sur cette Freebox !", lang),
+            Message::ErrorLcdOrientation => write!(f, "L'orientation doit valoir 0, 90, 180 ou 270 !"),
+            Message::ErrorLcdOrientationForced => write!(f, "L'orientation de l'écran est imposée par la Freebox !"),
+            Message::ErrorResourceNotFound => write!(f, "Ressource inconnue de la Freebox !"),
+            Message::ErrorBadRequest => write!(f, "Requête invalide !"),
+            Message::ErrorBoxInternal => write!(f, "Erreur interne de la Freebox !"),
+            Message::ErrorParseBody => write!(f, "Réponse de la Freebox illisible !"),
+            Message::ErrorRequestFailed => write!(f, "Échec de l'envoi de la requête !"),
+            Message::ErrorSecretStoreUnavailable => write!(f, "Coffre de secrets indisponible !"),
+            Message::ErrorInvalidPassphrase => write!(f, "Phrase de passe invalide !"),
+            Message::ErrorSecretStoreCorrupted => write!(f, "Coffre de secrets corrompu !"),
+            Message::ErrorConfigWrite(path, error) => write!(f, "Impossible d'écrire la configuration {} ({}) !", path, error),
+            Message::ErrorConfirmationRequired => write!(f, "Impossible de demander confirmation, relancez avec --confirm !"),
+        }
+    }
+
+    fn en(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Message::HelpAbout => write!(f, "A CLI to manage your Freebox"),
+            Message::HelpTemplate => write!(
+                f,
+                "\
+{{bin}} {{version}}
+{{author}}
+{{about}}
+
+\x1b[1mUSAGE:\x1b[0m
+    {{usage}}
+
+\x1b[1mCOMMANDS:\x1b[0m
+{{subcommands}}
+
+\x1b[1mOPTIONS:\x1b[0m
+{{options}}
+
+For more information, use '{{bin}} <command> --help'"
+            ),
+            Message::HelpVerbose => write!(f, "Print the requests sent on stderr (-vv: with their bodies)"),
+            Message::HelpQuiet => write!(f, "Print the results only"),
+            Message::HelpNoColor => write!(f, "Disable colors (also with NO_COLOR)"),
+            Message::HelpOutput => write!(f, "Output format of the results"),
+            Message::HelpListColumns => write!(f, "Columns to show, separated by commas (API field names)"),
+            Message::HelpListFilter => write!(f, "Keep the rows where field=value (or field!=value), repeatable"),
+            Message::HelpListSortBy => write!(f, "Sort the rows by this field"),
+            Message::HelpListNoHeaders => write!(f, "Do not print the headers"),
+            Message::HelpDhcpStatic => write!(f, "List the static leases instead of the dynamic ones"),
+            Message::HelpConfigResetConfirm => write!(f, "Reset the application settings"),
+            Message::HelpConfigLocale => write!(f, "Language of the CLI messages"),
+            Message::HelpInitTime => write!(f, "Timeout of the device discovery"),
+            Message::HelpAuthUrl => write!(f, "Url of the Freebox"),
+            Message::HelpAuthResume => write!(f, "Continue a pending authorization request"),
+            Message::HelpAuthWait => write!(f, "Maximum time to wait for the authorization, in seconds"),
+            Message::HelpAuthForgetApp => write!(f, "Also remove the application token"),
+            Message::HelpAuthShowToken => write!(f, "Show the session token in clear text"),
+            Message::HelpSettingsNoSelect => {
+                write!(f, "List the Freebox devices without picking one")
+            }
+            Message::HelpSettingsBypassTest => write!(f, "Do not test the provided url"),
+            Message::HelpHardwareTempWarn => {
+                write!(f, "Temperature (°C) from which a sensor is in warning")
+            }
+            Message::HelpHardwareTempCrit => {
+                write!(f, "Temperature (°C) from which a sensor is critical")
+            }
+            Message::HelpHardwareFanMin => write!(f, "Minimum speed (rpm) of a running fan"),
+            Message::HelpLcdBrightness => write!(f, "Screen brightness (0-100)"),
+            Message::HelpLcdOrientation => write!(f, "Screen orientation (0, 90, 180 or 270)"),
+            Message::HelpLcdHideWifiKey => write!(f, "Hide the Wi-Fi key on the screen"),
+            Message::HelpAirMediaEnabled => write!(f, "Enable or disable AirMedia"),
+            Message::HelpAirMediaPassword => write!(f, "AirMedia password of the Freebox"),
+            Message::HelpAirMediaPlayType => write!(f, "Type of media to play"),
+            Message::HelpAirMediaStopType => write!(f, "Type of media to stop"),
+            Message::HelpAirMediaPosition => write!(f, "Start position (in seconds)"),
+            Message::HelpAirMediaReceiverPassword => write!(f, "Receiver password"),
+            Message::HelpLang => write!(f, "Language of the Freebox interface (e.g. fra, eng)"),
+            Message::HelpConfirmYes => write!(f, "Do not ask for confirmation"),
+            Message::HelpNotificationTargetId => write!(f, "Target identifier"),
+            Message::HelpNotificationUnusedSince => write!(f, "Remove targets unused for N days"),
+            Message::HelpNotificationSubscriptions => {
+                write!(f, "Comma separated subscriptions (e.g. security,phone)")
+            }
+            Message::HelpNotificationDeviceId => write!(f, "Unique device identifier"),
+            Message::HelpNotificationDeviceType => write!(f, "Device type (e.g. android, ios)"),
+            Message::HelpNotificationDeviceName => write!(f, "Device name"),
+            Message::HelpNotificationToken => write!(f, "Push notification token of the device"),
+            Message::HelpNotificationBoxId => {
+                write!(f, "Freebox identifier (uid), detected when missing")
+            }
+            Message::HelpDiscoverSubnet => write!(
+                f,
+                "Subnet probed over HTTP when mDNS fails (e.g. 192.168.1.0/24)"
+            ),
+            Message::HelpDiscoverHttp => write!(f, "Probe over HTTP directly, without mDNS"),
+            Message::HelpHttpsCaFile => {
+                write!(f, "PEM file of root authorities to use instead of the bundled Freebox ones")
+            }
+            Message::HelpProfile => write!(f, "Profile of the Freebox to use"),
+            Message::HelpProfileName => write!(f, "Name of the profile"),
+            Message::HelpProfileUrl => write!(f, "Url of the profile Freebox"),
+            Message::HelpConfigPath => write!(f, "Configuration file to use"),
+            Message::HelpRequestTimeout => write!(f, "Maximum duration of a request to the Freebox, in seconds"),
+            Message::HelpRetries => write!(f, "Number of retries of failed GET, PUT and DELETE requests (never of POST)"),
+            Message::HelpWifiShowKeys => write!(f, "Show the Wi-Fi keys in clear text"),
+            Message::HelpWifiBssId => write!(f, "Network id (BSSID)"),
+            Message::HelpWifiSsid => write!(f, "Network name (SSID)"),
+            Message::HelpWifiKey => write!(f, "Network key"),
+            Message::HelpWifiEncryption => write!(f, "Network encryption"),
+
+            Message::NotImplemented => write!(f, "Command not available yet"),
+
+            Message::AuthWaitingGrant => write!(
+                f,
+                "Waiting for the application to be accepted on the Freebox..."
+            ),
+            Message::AuthSuccess => write!(f, "Authentication successful!"),
+            Message::AuthFailed => write!(f, "Authentication failed!"),
+            Message::AuthLoginNotInitialized => write!(f, "Login not initialized"),
+            Message::AuthSessionNotInitialized => write!(f, "Session not initialized"),
+            Message::AuthSessionToken(token) => write!(f, "Session token: {}", token),
+            Message::AuthStatusUrl(url) => write!(f, "Freebox URL: {}", url),
+            Message::AuthStatusApi(version, path) => write!(f, "API version: {} ({})", version, path),
+            Message::AuthStatusApp(app_id) => write!(f, "Application: {}", app_id),
+            Message::AuthStatusPending(track_id) => write!(f, "Authorization {} is pending, resume it with `fbx auth login --resume`", track_id),
+            Message::AuthStatusGrantedAt(date) => write!(f, "Granted on: {}", date),
+            Message::AuthStatusSessionAge(hours, minutes) => write!(f, "Session age: {}h {:02}min", hours, minutes),
+            Message::AuthStatusLoggedIn(logged_in) => write!(f, "Logged in: {}", logged_in),
+            Message::AuthStatusTokenValid => write!(f, "Session token is valid"),
+            Message::AuthStatusTokenExpired => write!(f, "Session token has expired, a new session will be opened by the next command"),
+            Message::AuthStatusBoxUnreachable => write!(f, "Freebox unreachable, token validity unknown"),
+            Message::AuthWaitingGrantOnBox(track_id) => write!(f, "Accept request #{} on the Freebox screen...", track_id),
+            Message::AuthDenied => write!(f, "The application was denied on the Freebox!"),
+            Message::AuthBoxTimeout => write!(f, "The request expired on the Freebox, run `fbx auth login` again!"),
+            Message::AuthUnknownRequest => write!(f, "The Freebox does not know this request, run `fbx auth login` again!"),
+            Message::AuthStillPending => write!(f, "Still pending, continue later with `fbx auth login --resume`."),
+            Message::AuthNothingToResume => write!(f, "No pending authorization request!"),
+            Message::AuthLoggedOut => write!(f, "Session closed!"),
+            Message::AuthLogoutUnreachable => write!(f, "The Freebox is unreachable, the session was forgotten and will expire on its own."),
+            Message::AuthNoSession => write!(f, "No open session."),
+            Message::AuthAppForgotten(app_id) => write!(f, "The token of application {} was removed.", app_id),
+            Message::AuthRevokeHint => write!(f, "To revoke the application, open Freebox OS > Freebox settings > Access management > Applications and delete it."),
+            Message::AuthMissingPermission(permission) => write!(f, "This app lacks the `{}` permission, grant it in Freebox OS > Freebox settings > Access management > Applications.", permission),
+            Message::AuthPermission => write!(f, "Permission"),
+            Message::AuthPermissionGranted => write!(f, "Granted"),
+            Message::AuthPermissionsUnknown => write!(f, "Unknown permissions, they will be known when the next session is opened."),
+
+            Message::ConfigUrl(url) => write!(f, "Url: {}", url),
+            Message::ConfigUrlUpdated => write!(f, "Url updated!"),
+            Message::ConfigBoxChanged => write!(f, "Another Freebox, the session and the app token of the previous one are forgotten."),
+            Message::ConfigInvalidUrl => write!(f, "Invalid url provided!"),
+            Message::ConfigResetConfirm => {
+                write!(f, "Are you sure you want to reset the configuration?")
+            }
+            Message::ConfigResetting => write!(f, "Resetting configuration..."),
+            Message::ConfigResetDone => write!(f, "Configuration reset!"),
+            Message::ConfigLocaleUpdated(locale) => {
+                write!(f, "CLI language updated: {}!", locale)
+            }
+
+            Message::HardwareInternalDisk(status) => write!(f, "Internal disk: {}", status),
+            Message::HardwareDiskProblem(status) => write!(f, "internal disk {}", status),
+            Message::HardwareExpansionProblem(slot, kind) => {
+                write!(f, "expansion {} ({})", slot, kind)
+            }
+            Message::HardwareFanSpeed(speed) => write!(f, "{} rpm", speed),
+            Message::HardwareSlot => write!(f, "Slot"),
+            Message::HardwareType => write!(f, "Type"),
+            Message::HardwarePresent => write!(f, "Present"),
+            Message::HardwareSupported => write!(f, "Supported"),
+            Message::HardwareStatus => write!(f, "Status"),
+            Message::HardwareSensor => write!(f, "Sensor"),
+            Message::HardwareValue => write!(f, "Value"),
+            Message::SystemRebootConfirm => write!(f, "Reboot the Freebox? The connection will be down for a few minutes."),
+            Message::SystemRebooting => write!(f, "The Freebox is rebooting..."),
+            Message::SystemShutdownConfirm => write!(f, "Shut down the Freebox? It can only be turned back on on site."),
+            Message::SystemShuttingDown => write!(f, "The Freebox is shutting down..."),
+
+            Message::LcdUpdated => write!(f, "Screen updated!"),
+            Message::LcdBrightness(brightness) => write!(f, "Brightness: {}%", brightness),
+            Message::LcdOrientation(orientation, forced) => write!(
+                f,
+                "Orientation: {}°{}",
+                orientation,
+                if *forced { " (forced)" } else { "" }
+            ),
+            Message::LcdHideWifiKey(hidden) => write!(f, "Wi-Fi key hidden: {}", hidden),
+
+            Message::AirMediaEnabled(enabled) => write!(f, "Enabled: {}", enabled),
+            Message::AirMediaConfigUpdated => write!(f, "AirMedia configuration updated!"),
+            Message::AirMediaName => write!(f, "Name"),
+            Message::AirMediaPassword => write!(f, "Password"),
+            Message::AirMediaPhoto => write!(f, "Photo"),
+            Message::AirMediaAudio => write!(f, "Audio"),
+            Message::AirMediaVideo => write!(f, "Video"),
+            Message::AirMediaScreen => write!(f, "Screen"),
+            Message::AirMediaPlaying(receiver) => write!(f, "Playback started on {}!", receiver),
+            Message::AirMediaStopped(receiver) => write!(f, "Playback stopped on {}!", receiver),
+            Message::AirMediaPasswordPrompt(receiver) => write!(f, "Password for {}:", receiver),
+
+            Message::LangCurrent(lang) => write!(f, "Language: {}", lang),
+            Message::LangAvailable(langs) => write!(f, "Available: {}", langs),
+            Message::LangAlreadySet(lang) => write!(f, "The language is already {}!", lang),
+            Message::LangUpdated(lang) => write!(f, "Language updated: {}!", lang),
+
+            Message::NotificationId => write!(f, "Id"),
+            Message::NotificationName => write!(f, "Name"),
+            Message::NotificationType => write!(f, "Type"),
+            Message::NotificationLastUse => write!(f, "Last use"),
+            Message::NotificationSubscriptions => write!(f, "Subscriptions"),
+            Message::NotificationNever => write!(f, "never"),
+            Message::NotificationNoTarget => write!(f, "No target to remove"),
+            Message::NotificationRemoveConfirm(count) => {
+                write!(f, "Remove {} notification target(s)?", count)
+            }
+            Message::NotificationRemoved(name) => write!(f, "Target {} removed!", name),
+            Message::NotificationSubscriptionsUpdated(name) => {
+                write!(f, "Subscriptions of {} updated!", name)
+            }
+            Message::NotificationRegistered(id) => write!(f, "Target {} registered!", id),
+
+            Message::DiscoverSearching => {
+                write!(f, "Looking for Freebox devices on the network...")
+            }
+            Message::DiscoverNoneFound => write!(f, "No Freebox found"),
+            Message::DiscoverSelect => write!(f, "Which Freebox do you want to use?"),
+            Message::DiscoverModel => write!(f, "Model"),
+            Message::DiscoverAddress => write!(f, "Address"),
+            Message::DiscoverUid => write!(f, "Uid"),
+            Message::DiscoverApiVersion => write!(f, "API version"),
+            Message::DiscoverApiDomain => write!(f, "API domain"),
+
+            Message::DiscoverInvalidSubnet(prefix) => write!(
+                f,
+                "Invalid subnet, expected a.b.c.d/n with n >= {} (e.g. 192.168.1.0/24)",
+                prefix
+            ),
+            Message::DiscoverProbing => write!(f, "Looking for Freebox devices over HTTP..."),
+
+            Message::HttpsEnabled(url) => {
+                write!(f, "HTTPS enabled, the Freebox is reachable at {}!", url)
+            }
+            Message::HttpsDisabled => write!(f, "HTTPS disabled!"),
+            Message::HttpsUnavailable => write!(f, "This Freebox does not offer HTTPS access!"),
+            Message::HttpsCaFileRequired => write!(
+                f,
+                "No Freebox root authority is bundled, give a PEM file with --ca-file!"
+            ),
+
+            Message::RemoteAccess(enabled) => write!(f, "Remote access: {}", enabled),
+            Message::RemoteApiAccess(enabled) => write!(f, "Remote API access: {}", enabled),
+            Message::RemoteIp(ip) => write!(f, "Public IP: {}", ip),
+            Message::RemotePort(port) => write!(f, "Remote HTTPS port: {}", port),
+            Message::RemoteEndpoint(url) => write!(f, "Recorded remote endpoint: {}", url),
+            Message::RemoteApiEnabled(url) => write!(f, "Remote API access enabled, the Freebox is reachable at {}!", url),
+            Message::RemoteApiDisabled => write!(f, "Remote API access disabled!"),
+            Message::RemoteNotConfigured => write!(f, "No remote endpoint, run `fbx connection remote-api enable` from the local network!"),
+            Message::RemoteModeEnabled(url) => write!(f, "Remote mode enabled, the Freebox will be reached at {}!", url),
+            Message::RemoteModeDisabled => write!(f, "Remote mode disabled!"),
+
+            Message::ProfileName => write!(f, "Profile"),
+            Message::ProfileUrl => write!(f, "Url"),
+            Message::ProfileApp => write!(f, "Application"),
+            Message::ProfileCurrent => write!(f, "Default"),
+            Message::ProfileAppGranted => write!(f, "granted"),
+            Message::ProfileAppPending => write!(f, "pending"),
+            Message::ProfileAppNone => write!(f, "none"),
+            Message::ProfileAdded(name) => write!(f, "Profile {} added!", name),
+            Message::ProfileExists(name) => write!(f, "Profile {} already exists!", name),
+            Message::ProfileUnknown(name) => write!(f, "Unknown profile {}!", name),
+            Message::ProfileUsed(name) => write!(f, "Profile {} is now used by default!", name),
+            Message::ProfileRemoveConfirm(name) => write!(f, "Remove profile {} and its application?", name),
+            Message::ProfileRemoved(name) => write!(f, "Profile {} removed!", name),
+            Message::ProfileInUse(name) => write!(f, "Profile {} is in use, pick another one before removing it!", name),
+
+            Message::SecretPassphrase => write!(f, "Secret store passphrase:"),
+            Message::SecretStoreFailed(error) => write!(f, "The app token could not be moved to the secret store, the configuration is not saved ({})", error),
+
+            Message::OutputField => write!(f, "Field"),
+            Message::OutputValue => write!(f, "Value"),
+            Message::ListUnknownField(field, fields) => write!(f, "Unknown field: {}, available fields: {}", field, fields),
+
+            Message::DeviceName => write!(f, "Name"),
+            Message::DeviceType => write!(f, "Type"),
+            Message::DeviceIp => write!(f, "IP"),
+            Message::DeviceMac => write!(f, "MAC"),
+            Message::DeviceVendor => write!(f, "Vendor"),
+            Message::DeviceActive => write!(f, "Active"),
+
+            Message::DhcpHostname => write!(f, "Hostname"),
+            Message::DhcpStatic => write!(f, "Static"),
+            Message::DhcpRemaining => write!(f, "Lease remaining"),
+            Message::DhcpComment => write!(f, "Comment"),
+
+            Message::RedirectionEnabled => write!(f, "Enabled"),
+            Message::RedirectionId => write!(f, "Id"),
+            Message::RedirectionProtocol => write!(f, "Protocol"),
+            Message::RedirectionWanPorts => write!(f, "WAN ports"),
+            Message::RedirectionLanIp => write!(f, "LAN IP"),
+            Message::RedirectionLanPort => write!(f, "LAN port"),
+            Message::RedirectionSource => write!(f, "Source IP"),
+
+            Message::VmName => write!(f, "Name"),
+            Message::VmId => write!(f, "Id"),
+            Message::VmStatus => write!(f, "Status"),
+            Message::VmMemory => write!(f, "Memory (MB)"),
+            Message::VmCpus => write!(f, "vCPUs"),
+            Message::VmOs => write!(f, "OS"),
+
+            Message::WifiEnabled(enabled) => write!(f, "Wi-Fi enabled: {}", enabled),
+            Message::WifiMacFilter(state) => write!(f, "MAC filtering: {}", state),
+            Message::WifiTurnedOn => write!(f, "Wi-Fi turned on!"),
+            Message::WifiTurnedOff => write!(f, "Wi-Fi turned off!"),
+            Message::WifiApId => write!(f, "Id"),
+            Message::WifiApName => write!(f, "Name"),
+            Message::WifiBand => write!(f, "Band"),
+            Message::WifiState => write!(f, "State"),
+            Message::WifiChannel => write!(f, "Channel"),
+            Message::WifiChannelWidth => write!(f, "Width (MHz)"),
+            Message::WifiBssId => write!(f, "BSSID"),
+            Message::WifiBssAp => write!(f, "Access point"),
+            Message::WifiSsid => write!(f, "SSID"),
+            Message::WifiEncryption => write!(f, "Encryption"),
+            Message::WifiKey => write!(f, "Key"),
+            Message::WifiStations => write!(f, "Clients"),
+            Message::WifiBssUpdated(ssid) => write!(f, "Network {} updated!", ssid),
+            Message::WifiBssDefaultConfig => write!(f, "Networks using the default configuration are updated too."),
+
+            Message::ErrorFailure => write!(f, "Unexpected error"),
+            Message::ErrorInvalidInput => write!(f, "Invalid input"),
+            Message::ErrorAuthRequired => write!(f, "Authentication required, run `fbx auth login`"),
+            Message::ErrorPermissionDenied => write!(f, "Permission denied"),
+            Message::ErrorNotFound => write!(f, "Not found"),
+            Message::ErrorTimeout => write!(f, "The Freebox did not answer in time"),
+            Message::ErrorUnreachable => write!(f, "The Freebox cannot be reached, check its url with `fbx config info`"),
+            Message::ErrorBox => write!(f, "The Freebox rejected the request"),
+            Message::ErrorDetail(error, detail) => write!(f, "{}: {}", error, detail),
+
+            Message::ErrorUnknownField(field) => write!(f, "Unknown field `{}`!", field),
+            Message::ErrorInvalidFilter(filter) => write!(f, "Invalid filter `{}`, expected field=value or field!=value!", filter),
+            Message::ErrorNothingToUpdate(options) => write!(f, "Nothing to update ({})!", options),
+            Message::ErrorMissingChallenge => write!(f, "Missing challenge!"),
+            Message::ErrorSignChallenge => write!(f, "Could not sign the challenge with the app token!"),
+            Message::ErrorCaFileNotFound(path) => write!(f, "Root certificate {} not found!", path),
+            Message::ErrorInvalidCaFile => write!(f, "Invalid root certificate!"),
+            Message::ErrorUnexpectedHttpsBox => write!(f, "Unexpected Freebox behind HTTPS!"),
+            Message::ErrorInvalidApiVersion(version) => write!(f, "Invalid API version {}!", version),
+            Message::ErrorInvalidUnusedSince(days) => write!(f, "Invalid duration of {} days!", days),
+            Message::ErrorMdnsUnavailable => write!(f, "mDNS is unavailable!"),
+            Message::ErrorNotificationTargetNotFound(id) => write!(f, "Notification target {} not found!", id),
+            Message::ErrorNotificationTargetRequired => write!(f, "Give an id or --unused-since!"),
+            Message::ErrorWifiBssNotFound(id) => write!(f, "Wi-Fi network {} not found!", id),
+            Message::ErrorWifiInvalidKey(encryption) => write!(f, "Invalid Wi-Fi key for the {} encryption!", encryption),
+            Message::ErrorAirMediaReceiverNotFound(name) => write!(f, "AirMedia receiver {} not found!", name),
+            Message::ErrorAirMediaUnsupportedMedia => write!(f, "This receiver does not support this media type!"),
+            Message::ErrorAirMediaPasswordRequired => write!(f, "The receiver password is required!"),
+            Message::ErrorLangUnavailable(lang) => write!(f, "Language {} is not available on this Freebox!", lang),
+            Message::ErrorLcdOrientation => write!(f, "The orientation must be 0, 90, 180 or 270!"),
+            Message::ErrorLcdOrientationForced => write!(f, "The screen orientation is forced by the Freebox!"),
+            Message::ErrorResourceNotFound => write!(f, "Resource unknown to the Freebox!"),
+            Message::ErrorBadRequest => write!(f, "Bad request!"),
+            Message::ErrorBoxInternal => write!(f, "Internal Freebox error!"),
+            Message::ErrorParseBody => write!(f, "Unreadable Freebox answer!"),
+            Message::ErrorRequestFailed => write!(f, "The request could not be sent!"),
+            Message::ErrorSecretStoreUnavailable => write!(f, "Secret store unavailable!"),
+            Message::ErrorInvalidPassphrase => write!(f, "Invalid passphrase!"),
+            Message::ErrorSecretStoreCorrupted => write!(f, "Corrupted secret store!"),
+            Message::ErrorConfigWrite(path, error) => write!(f, "Could not write the configuration {} ({})!", path, error),
+            Message::ErrorConfirmationRequired => write!(f, "Cannot ask for confirmation, run again with --confirm!"),
+        }
+    }
+}
+
+impl Display for Message<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match locale() {
+            Locale::Fr => self.fr(f),
+            Locale::En => self.en(f),
+        }
+    }
+}
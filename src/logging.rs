@@ -0,0 +1,92 @@
+use crate::config::env_var;
+use inquire::ui::RenderConfig;
+use spinners::{Spinner, Spinners, Stream};
+use std::fmt::Display;
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+
+static VERBOSITY: OnceLock<Verbosity> = OnceLock::new();
+static NO_COLOR: OnceLock<bool> = OnceLock::new();
+
+/// How much is printed besides the results, set by `-q` and `-v`/`-vv`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Verbosity {
+    /// Results only.
+    Quiet,
+    #[default]
+    Normal,
+    /// Method, url, status and timing of every request.
+    Requests,
+    /// Bodies of the requests and responses too.
+    Bodies,
+}
+
+/// Sets the verbosity and colors for the whole run, `NO_COLOR` disables
+/// colors like `--no-color` does (https://no-color.org).
+pub fn init(verbose: u8, quiet: bool, no_color: bool) {
+    let verbosity = match (quiet, verbose) {
+        (true, _) => Verbosity::Quiet,
+        (false, 0) => Verbosity::Normal,
+        (false, 1) => Verbosity::Requests,
+        (false, _) => Verbosity::Bodies,
+    };
+    let _ = VERBOSITY.set(verbosity);
+    let no_color = no_color || env_var("NO_COLOR").is_some();
+    let _ = NO_COLOR.set(no_color);
+    if no_color {
+        inquire::set_global_render_config(RenderConfig::empty());
+    }
+}
+
+pub fn verbosity() -> Verbosity {
+    VERBOSITY.get().copied().unwrap_or_default()
+}
+
+pub fn is_quiet() -> bool {
+    verbosity() == Verbosity::Quiet
+}
+
+pub fn no_color() -> bool {
+    NO_COLOR.get().copied().unwrap_or_default()
+}
+
+/// Writes a diagnostic on stderr when running at least at `level`, stdout is
+/// kept for the results.
+pub fn debug(level: Verbosity, message: impl Display) {
+    if verbosity() >= level {
+        eprintln!("{}", message);
+    }
+}
+
+/// Spinner shown on stderr while waiting, unless `--quiet` is set or stderr
+/// is not a terminal, stdout is kept clean for the results.
+pub struct Progress(Option<Spinner>);
+
+impl Progress {
+    pub fn start(message: impl Display) -> Self {
+        match is_quiet() || !std::io::stderr().is_terminal() {
+            true => Progress(None),
+            false => Progress(Some(Spinner::with_stream(
+                Spinners::Dots,
+                message.to_string(),
+                Stream::Stderr,
+            ))),
+        }
+    }
+
+    pub fn stop(&mut self) {
+        if let Some(spinner) = self.0.as_mut() {
+            spinner.stop_with_newline();
+        }
+    }
+}
+
+/// Prints a status message on stdout, unless `--quiet` is set.
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => {
+        if !$crate::logging::is_quiet() {
+            println!($($arg)*);
+        }
+    };
+}
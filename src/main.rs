@@ -1,56 +1,154 @@
 use crate::app::App;
+use crate::handlers::air_media::AirMedia;
 use crate::handlers::auth::Auth;
 use crate::handlers::config::Config;
+use crate::handlers::connection::Connection;
 use crate::handlers::device::Device;
+use crate::handlers::dhcp::Dhcp;
+use crate::handlers::lang::Lang;
+use crate::handlers::lcd::Lcd;
+use crate::handlers::notification::Notification;
+use crate::handlers::redirection::Redirection;
 use crate::handlers::settings::Settings;
 use crate::handlers::system::System;
 use crate::handlers::vm::Vm;
+use crate::handlers::wifi::Wifi;
 use crate::models::args::{
-    AuthCommands, Commands, ConfigCommands, DevicesCommands, SettingsCommands, SystemCommands,
-    VmSubCommands,
+    AirMediaCommands, AirMediaConfigCommands, AuthCommands, Commands, ConfigCommands,
+    ConfigHttpsCommands, ConfigProfileCommands, ConfigRemoteCommands, ConnectionCommands,
+    ConnectionRemoteApiCommands, DevicesCommands, DhcpCommands, LangCommands, LcdCommands,
+    NotificationCommands, NotificationTargetsCommands, RedirectionCommands, SettingsCommands,
+    SystemCommands, VmSubCommands, WifiApCommands, WifiBssCommands, WifiCommands,
 };
+use crate::models::exception::ClientError;
 
 mod app;
 mod client;
 mod config;
+#[cfg(test)]
+mod fake;
 mod handlers;
+mod i18n;
+mod logging;
 mod models;
+mod output;
 mod services;
 
 #[tokio::main]
 async fn main() -> Result<(), std::io::Error> {
     let mut app = App::default();
-    app.initialize().await;
-    let a = match app.cli.cmd.clone() {
+    if let Err(e) = app.initialize().await {
+        exit(&e);
+    }
+    let result = run(&mut app).await;
+    let saved = app.save();
+    if let Err(e) = result.and(saved) {
+        exit(&e);
+    }
+    Ok(())
+}
+
+/// Runs the command given on the command line.
+async fn run(app: &mut App) -> Result<(), ClientError> {
+    match app.cli.cmd.clone() {
         Commands::Vm { cmd } => match cmd {
-            VmSubCommands::List(args) => Vm::list(&mut app, &args).await,
-            VmSubCommands::Get(args) => Vm::get(&mut app, &args).await,
+            VmSubCommands::List(args) => Vm::list(app, &args).await,
+            VmSubCommands::Get(args) => Vm::get(app, &args).await,
         },
         Commands::Device { cmd } => match cmd {
-            DevicesCommands::List => Device::list(&mut app).await,
+            DevicesCommands::List(args) => Device::list(app, &args).await,
         },
         Commands::Auth { cmd } => match cmd {
-            AuthCommands::Status => Auth::status(&mut app).await,
-            AuthCommands::Logout => Auth::logout(&mut app).await,
-            AuthCommands::Login(args) => Auth::login(&mut app, args).await,
-            AuthCommands::SetUrl(_args) => Auth::set_url(&mut app).await,
-            AuthCommands::Init(_args) => Auth::init(&mut app).await,
+            AuthCommands::Status(args) => Auth::status(app, &args).await,
+            AuthCommands::Logout(args) => Auth::logout(app, &args).await,
+            AuthCommands::Login(args) => Auth::login(app, args).await,
+            AuthCommands::SetUrl(args) => Auth::set_url(app, args).await,
         },
         Commands::Settings { cmd } => match cmd {
-            SettingsCommands::Reset { .. } => Settings::reset(&mut app).await,
-            SettingsCommands::SetUrl { .. } => Settings::set_url(&mut app).await,
-            SettingsCommands::Discover { .. } => Settings::discover(&mut app).await,
-            SettingsCommands::GetUrl => Settings::get_url(&mut app).await,
+            SettingsCommands::Reset { .. } => Settings::reset(app).await,
+            SettingsCommands::SetUrl { .. } => Settings::set_url(app).await,
+            SettingsCommands::Discover(args) => Settings::discover(app, &args).await,
+            SettingsCommands::GetUrl => Settings::get_url(app).await,
         },
         Commands::System { cmd } => match cmd {
-            SystemCommands::Reboot => System::reboot(&mut app).await,
-            SystemCommands::Shutdown => System::shutdown(&mut app).await,
-            SystemCommands::Get => System::get(&mut app).await,
+            SystemCommands::Reboot(args) => System::reboot(app, &args).await,
+            SystemCommands::Shutdown(args) => System::shutdown(app, &args).await,
+            SystemCommands::Get => System::get(app).await,
+            SystemCommands::Hardware(args) => System::hardware(app, &args).await,
+        },
+        Commands::Lcd { cmd } => match cmd {
+            LcdCommands::Get => Lcd::get(app).await,
+            LcdCommands::Set(args) => Lcd::set(app, &args).await,
+        },
+        Commands::Lang { cmd } => match cmd {
+            LangCommands::Get => Lang::get(app).await,
+            LangCommands::Set(args) => Lang::set(app, &args).await,
+        },
+        Commands::Notification { cmd } => match cmd {
+            NotificationCommands::Targets { cmd } => match cmd {
+                NotificationTargetsCommands::List(args) => Notification::list(app, &args).await,
+                NotificationTargetsCommands::Rm(args) => Notification::remove(app, &args).await,
+                NotificationTargetsCommands::UpdateSubscriptions(args) => {
+                    Notification::update_subscriptions(app, &args).await
+                }
+            },
+            NotificationCommands::Register(args) => Notification::register(app, &args).await,
+        },
+        Commands::Connection { cmd } => match cmd {
+            ConnectionCommands::RemoteApi { cmd } => match cmd {
+                ConnectionRemoteApiCommands::Get => Connection::remote_api(app).await,
+                ConnectionRemoteApiCommands::Enable(args) => {
+                    Connection::enable_remote_api(app, &args).await
+                }
+                ConnectionRemoteApiCommands::Disable => Connection::disable_remote_api(app).await,
+            },
+        },
+        Commands::Dhcp { cmd } => match cmd {
+            DhcpCommands::Leases(args) => Dhcp::leases(app, &args).await,
+        },
+        Commands::Redirection { cmd } => match cmd {
+            RedirectionCommands::List(args) => Redirection::list(app, &args).await,
+        },
+        Commands::AirMedia { cmd } => match cmd {
+            AirMediaCommands::Config { cmd } => match cmd {
+                AirMediaConfigCommands::Get => AirMedia::get_config(app).await,
+                AirMediaConfigCommands::Set(args) => AirMedia::set_config(app, &args).await,
+            },
+            AirMediaCommands::Receivers(args) => AirMedia::receivers(app, &args).await,
+            AirMediaCommands::Play(args) => AirMedia::play(app, &args).await,
+            AirMediaCommands::Stop(args) => AirMedia::stop(app, &args).await,
+        },
+        Commands::Wifi { cmd } => match cmd {
+            WifiCommands::Status => Wifi::status(app).await,
+            WifiCommands::On => Wifi::set_enabled(app, true).await,
+            WifiCommands::Off => Wifi::set_enabled(app, false).await,
+            WifiCommands::Ap { cmd } => match cmd {
+                WifiApCommands::List(args) => Wifi::aps(app, &args).await,
+            },
+            WifiCommands::Bss { cmd } => match cmd {
+                WifiBssCommands::List(args) => Wifi::bss(app, &args).await,
+                WifiBssCommands::Set(args) => Wifi::set_bss(app, &args).await,
+            },
         },
         Commands::Config { cmd } => match cmd {
-            ConfigCommands::SetUrl(args) => Config::change_url(&mut app, args.url).await,
-            ConfigCommands::Info => Config::show(&mut app).await,
-            ConfigCommands::Reset(args) => Config::reset(&mut app, args.confirm).await,
+            ConfigCommands::SetUrl(args) => Config::change_url(app, args.url).await,
+            ConfigCommands::Info => Config::show(app).await,
+            ConfigCommands::SetLocale(args) => Config::set_locale(app, args.locale).await,
+            ConfigCommands::Reset(args) => Config::reset(app, args.confirm).await,
+            ConfigCommands::Https { cmd } => match cmd {
+                ConfigHttpsCommands::Enable(args) => Config::enable_https(app, &args).await,
+                ConfigHttpsCommands::Disable => Config::disable_https(app).await,
+            },
+            ConfigCommands::Profile { cmd } => match cmd {
+                ConfigProfileCommands::List(args) => Config::list_profiles(app, &args).await,
+                ConfigProfileCommands::Add(args) => Config::add_profile(app, &args).await,
+                ConfigProfileCommands::Use(args) => Config::use_profile(app, &args.name).await,
+                ConfigProfileCommands::Rm(args) => Config::remove_profile(app, &args).await,
+            },
+            ConfigCommands::Remote { cmd } => match cmd {
+                ConfigRemoteCommands::Enable => Config::enable_remote(app).await,
+                ConfigRemoteCommands::Disable => Config::disable_remote(app).await,
+            },
         },
         Commands::Info => {
             println!("{:?}", app);
@@ -61,9 +159,13 @@ async fn main() -> Result<(), std::io::Error> {
             Ok(())
         }
     }
-    .map_err(|x| {
-        println!("{:?}", x);
-    });
-    app.save();
-    Ok(())
+}
+
+/// Reports the error on stderr and exits with its code, see
+/// [`ClientError::exit_code`]. A failed check has already been reported.
+fn exit(error: &ClientError) -> ! {
+    if !matches!(error, ClientError::CheckFailed(_)) {
+        eprintln!("{}", error.message());
+    }
+    std::process::exit(error.exit_code());
 }
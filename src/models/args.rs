@@ -1,35 +1,42 @@
+use crate::i18n::{Locale, Message};
+use crate::models::freebox::air_media::AirMediaMediaType;
+use crate::models::freebox::wifi::WifiEncryption;
+use crate::output::OutputFormat;
+use crate::services::discover::Subnet;
 use clap::{ArgAction, Args, Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Parser, Debug, Clone)]
 #[command(name = "fbx")]
-#[command(help_template = "\
-{bin} {version}
-{author}
-{about}
+#[command(help_template = Message::HelpTemplate.to_string())]
+#[command(about = Message::HelpAbout.to_string(), long_about = None)]
+pub struct Cli {
+    #[clap(subcommand)]
+    pub cmd: Commands,
 
-\x1b[1mUSAGE:\x1b[0m
-    {usage}
+    #[arg(short, long, global = true, help = Message::HelpVerbose.to_string(), action = ArgAction::Count)]
+    pub verbose: u8,
 
-\x1b[1mCOMMANDS:\x1b[0m
-{subcommands}
+    #[arg(short, long, global = true, conflicts_with = "verbose", help = Message::HelpQuiet.to_string(), action = ArgAction::SetTrue)]
+    pub quiet: bool,
 
-\x1b[1mOPTIONS:\x1b[0m
-{options}
+    #[arg(short, long, global = true, help = Message::HelpNoColor.to_string(), action = ArgAction::SetTrue)]
+    pub no_color: bool,
 
-Pour plus d'informations, utilisez '{bin} <commande> --help'")]
-#[command(about = "Une CLI pour gérer les commandes fbx", long_about = None)]
-pub struct Cli {
-    #[clap(subcommand)]
-    pub cmd: Commands,
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Table, help = Message::HelpOutput.to_string())]
+    pub output: OutputFormat,
 
-    #[arg(short, long, help = "Affiche des informations supplémentaires", default_value = None, action = ArgAction::SetTrue)]
-    pub verbose: Option<bool>,
+    #[arg(long, global = true, env = "FBX_TIMEOUT", value_name = "SECONDS", default_value_t = 5, help = Message::HelpRequestTimeout.to_string())]
+    pub timeout: u64,
 
-    #[arg(short, long, help = "Supprime les messages d'erreur", default_value = None, action = ArgAction::SetTrue)]
-    pub quiet: Option<bool>,
+    #[arg(long, global = true, env = "FBX_RETRIES", default_value_t = 2, help = Message::HelpRetries.to_string())]
+    pub retries: u32,
 
-    #[arg(short, long, help = "Desactiver la couleur", default_value = None, action = ArgAction::SetTrue)]
-    pub no_color: Option<bool>,
+    #[arg(long, global = true, env = "FBX_PROFILE", help = Message::HelpProfile.to_string())]
+    pub profile: Option<String>,
+
+    #[arg(long, global = true, env = "FBX_CONFIG", help = Message::HelpConfigPath.to_string())]
+    pub config: Option<PathBuf>,
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -58,6 +65,39 @@ pub enum Commands {
         #[clap(subcommand)]
         cmd: SystemCommands,
     },
+    Lcd {
+        #[clap(subcommand)]
+        cmd: LcdCommands,
+    },
+    Lang {
+        #[clap(subcommand)]
+        cmd: LangCommands,
+    },
+    Notification {
+        #[clap(subcommand)]
+        cmd: NotificationCommands,
+    },
+    Connection {
+        #[clap(subcommand)]
+        cmd: ConnectionCommands,
+    },
+    Dhcp {
+        #[clap(subcommand)]
+        cmd: DhcpCommands,
+    },
+    Redirection {
+        #[clap(subcommand)]
+        cmd: RedirectionCommands,
+    },
+    #[command(name = "airmedia")]
+    AirMedia {
+        #[clap(subcommand)]
+        cmd: AirMediaCommands,
+    },
+    Wifi {
+        #[clap(subcommand)]
+        cmd: WifiCommands,
+    },
     Info,
 }
 
@@ -72,7 +112,10 @@ pub enum VmSubCommands {
 }
 
 #[derive(Args, Debug, Clone)]
-pub struct VmListArgs {}
+pub struct VmListArgs {
+    #[command(flatten)]
+    pub list: ListArgs,
+}
 
 #[derive(Args, Debug, Clone)]
 pub struct VmGetArgs {
@@ -83,7 +126,70 @@ pub struct VmGetArgs {
 pub enum ConfigCommands {
     Info,
     SetUrl(ConfigSetUrlArgs),
+    SetLocale(ConfigSetLocaleArgs),
     Reset(ConfigResetArgs),
+    Https {
+        #[clap(subcommand)]
+        cmd: ConfigHttpsCommands,
+    },
+    Remote {
+        #[clap(subcommand)]
+        cmd: ConfigRemoteCommands,
+    },
+    Profile {
+        #[clap(subcommand)]
+        cmd: ConfigProfileCommands,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ConfigProfileCommands {
+    List(ListArgs),
+    Add(ConfigProfileAddArgs),
+    Use(ConfigProfileNameArgs),
+    Rm(ConfigProfileRmArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ConfigProfileAddArgs {
+    #[arg(help = Message::HelpProfileName.to_string())]
+    pub name: String,
+
+    #[arg(short, long, help = Message::HelpProfileUrl.to_string())]
+    pub url: Option<String>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ConfigProfileNameArgs {
+    #[arg(help = Message::HelpProfileName.to_string())]
+    pub name: String,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ConfigProfileRmArgs {
+    #[arg(help = Message::HelpProfileName.to_string())]
+    pub name: String,
+
+    #[arg(short, long, help = Message::HelpConfirmYes.to_string(), default_value = None, action = ArgAction::SetTrue)]
+    pub yes: bool,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ConfigRemoteCommands {
+    Enable,
+    Disable,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ConfigHttpsCommands {
+    Enable(ConfigHttpsEnableArgs),
+    Disable,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ConfigHttpsEnableArgs {
+    #[arg(long, help = Message::HelpHttpsCaFile.to_string())]
+    pub ca_file: Option<String>,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -91,9 +197,15 @@ pub struct ConfigSetUrlArgs {
     pub url: String,
 }
 
+#[derive(Args, Debug, Clone)]
+pub struct ConfigSetLocaleArgs {
+    #[arg(help = Message::HelpConfigLocale.to_string(), value_enum)]
+    pub locale: Locale,
+}
+
 #[derive(Args, Debug, Clone)]
 pub struct ConfigResetArgs {
-    #[arg(short, long, help = "Réinitialise les paramètres de l'application", default_value = None, action = ArgAction::SetTrue)]
+    #[arg(short, long, help = Message::HelpConfigResetConfirm.to_string(), default_value = None, action = ArgAction::SetTrue)]
     pub confirm: bool,
 }
 
@@ -113,83 +225,370 @@ pub struct InfoVmArgs {
     pub uuid: String,
 }
 
+#[derive(Subcommand, Debug, Clone)]
+pub enum DevicesCommands {
+    List(ListArgs),
+}
+
+// Shapes the output of list commands, fields are named after the Freebox API,
+// nested ones with their path (`capabilities.video`).
+#[derive(Args, Debug, Clone, Default)]
+pub struct ListArgs {
+    #[arg(long, help = Message::HelpListColumns.to_string(), value_delimiter = ',')]
+    pub columns: Vec<String>,
+
+    #[arg(long, help = Message::HelpListFilter.to_string())]
+    pub filter: Vec<String>,
+
+    #[arg(long, help = Message::HelpListSortBy.to_string())]
+    pub sort_by: Option<String>,
+
+    #[arg(long, help = Message::HelpListNoHeaders.to_string(), default_value = None, action = ArgAction::SetTrue)]
+    pub no_headers: bool,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum AuthCommands {
+    Status(AuthStatusArgs),
+    Logout(AuthLogoutArgs),
+    Login(AuthLoginArgs),
+    SetUrl(AuthSetUrlArgs),
+}
+
 #[derive(Args, Debug, Clone)]
-pub struct InitArgs {
-    #[arg(short, long, help = "Définit l'adresse de la Freebox", default_value = None)]
+pub struct AuthSetUrlArgs {
+    pub url: String,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct AuthStatusArgs {
+    #[arg(long, help = Message::HelpAuthShowToken.to_string(), default_value = None, action = ArgAction::SetTrue)]
+    pub show_token: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct AuthLogoutArgs {
+    #[arg(long, help = Message::HelpAuthForgetApp.to_string(), default_value = None, action = ArgAction::SetTrue)]
+    pub forget_app: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct AuthLoginArgs {
+    #[arg(help = Message::HelpAuthUrl.to_string(), required_unless_present = "resume")]
     pub url: Option<String>,
-    #[arg(short, long, help = "Découvre les périphériques Freebox", default_value_t = true, action = ArgAction::SetFalse)]
-    pub discover: bool,
+
+    #[arg(long, help = Message::HelpAuthResume.to_string(), conflicts_with = "url", default_value = None, action = ArgAction::SetTrue)]
+    pub resume: bool,
 
     #[arg(
         short,
         long,
-        help = "Délai d'attente pour la découverte des périphériques",
+        value_name = "SECONDS",
+        help = Message::HelpAuthWait.to_string(),
+        default_value_t = 300
+    )]
+    pub wait: u64,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum SettingsCommands {
+    Discover(SettingsDiscoverArgs),
+    SetUrl {
+        url: String,
+        #[arg(short, long, help = Message::HelpSettingsBypassTest.to_string(), default_value = None, action = ArgAction::SetTrue)]
+        bypass_test: Option<bool>,
+    },
+    GetUrl,
+    Reset {
+        #[arg(short, long, help = Message::HelpConfigResetConfirm.to_string(), default_value = None, action = ArgAction::SetTrue)]
+        confirm: bool,
+    },
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SettingsDiscoverArgs {
+    #[command(flatten)]
+    pub list: ListArgs,
+
+    #[arg(
+        short,
+        long,
+        help = Message::HelpInitTime.to_string(),
         default_value_t = 5
     )]
     pub time: u64,
 
-    #[arg(short, long, help = "Ignore les erreurs de découverte", default_value = None, action = ArgAction::SetTrue)]
-    pub without_confirm: bool,
+    #[arg(long, help = Message::HelpSettingsNoSelect.to_string(), default_value = None, action = ArgAction::SetTrue)]
+    pub no_select: bool,
+
+    #[arg(long, help = Message::HelpDiscoverSubnet.to_string())]
+    pub subnet: Option<Subnet>,
+
+    #[arg(long, help = Message::HelpDiscoverHttp.to_string(), default_value = None, action = ArgAction::SetTrue)]
+    pub http: bool,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum SystemCommands {
+    Shutdown(SystemPowerArgs),
+    Reboot(SystemPowerArgs),
+    Get,
+    Hardware(SystemHardwareArgs),
+}
 
-    #[arg(short = 's', long = "bypass-status", help = "Bypass status checking for app", default_value = None, action = ArgAction::SetTrue)]
-    pub bypass_status: bool,
+#[derive(Args, Debug, Clone)]
+pub struct SystemPowerArgs {
+    #[arg(short, long, help = Message::HelpConfirmYes.to_string(), default_value = None, action = ArgAction::SetTrue)]
+    pub yes: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SystemHardwareArgs {
+    #[arg(
+        long,
+        help = Message::HelpHardwareTempWarn.to_string(),
+        default_value_t = 75
+    )]
+    pub temp_warn: i32,
 
     #[arg(
-        long = "app_id",
-        help = "Override default app_id",
-        default_value = "com.nikho.fbx-cli"
+        long,
+        help = Message::HelpHardwareTempCrit.to_string(),
+        default_value_t = 90
     )]
-    pub app_id: String,
+    pub temp_crit: i32,
 
-    #[arg(long = "device", help = "Override default device_name")]
-    pub device: Option<String>,
+    #[arg(
+        long,
+        help = Message::HelpHardwareFanMin.to_string(),
+        default_value_t = 500
+    )]
+    pub fan_min: i32,
 }
 
 #[derive(Subcommand, Debug, Clone)]
-pub enum DevicesCommands {
-    List,
+pub enum LcdCommands {
+    Get,
+    Set(LcdSetArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct LcdSetArgs {
+    #[arg(short, long, help = Message::HelpLcdBrightness.to_string(), value_parser = clap::value_parser!(i32).range(0..=100))]
+    pub brightness: Option<i32>,
+
+    #[arg(short, long, help = Message::HelpLcdOrientation.to_string())]
+    pub orientation: Option<i32>,
+
+    #[arg(long, help = Message::HelpLcdHideWifiKey.to_string())]
+    pub hide_wifi_key: Option<bool>,
 }
 
 #[derive(Subcommand, Debug, Clone)]
-pub enum AuthCommands {
-    Status,
-    Logout,
-    Login(AuthLoginArgs),
-    SetUrl(AuthSetUrlArgs),
-    Init(InitArgs),
+pub enum AirMediaCommands {
+    Config {
+        #[clap(subcommand)]
+        cmd: AirMediaConfigCommands,
+    },
+    Receivers(ListArgs),
+    Play(AirMediaPlayArgs),
+    Stop(AirMediaStopArgs),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum AirMediaConfigCommands {
+    Get,
+    Set(AirMediaConfigSetArgs),
 }
 
 #[derive(Args, Debug, Clone)]
-pub struct AuthSetUrlArgs {
-    pub url: String,
+pub struct AirMediaConfigSetArgs {
+    #[arg(short, long, help = Message::HelpAirMediaEnabled.to_string())]
+    pub enabled: Option<bool>,
+
+    #[arg(short, long, help = Message::HelpAirMediaPassword.to_string())]
+    pub password: Option<String>,
 }
 
 #[derive(Args, Debug, Clone)]
-pub struct AuthLoginArgs {
+pub struct AirMediaPlayArgs {
+    pub receiver: String,
     pub url: String,
+
+    #[arg(short = 't', long = "type", help = Message::HelpAirMediaPlayType.to_string(), value_enum, default_value_t = AirMediaMediaType::Video)]
+    pub media_type: AirMediaMediaType,
+
+    #[arg(long, help = Message::HelpAirMediaPosition.to_string())]
+    pub position: Option<i32>,
+
+    #[arg(short, long, help = Message::HelpAirMediaReceiverPassword.to_string())]
+    pub password: Option<String>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct AirMediaStopArgs {
+    pub receiver: String,
+
+    #[arg(short = 't', long = "type", help = Message::HelpAirMediaStopType.to_string(), value_enum, default_value_t = AirMediaMediaType::Video)]
+    pub media_type: AirMediaMediaType,
+
+    #[arg(short, long, help = Message::HelpAirMediaReceiverPassword.to_string())]
+    pub password: Option<String>,
 }
 
 #[derive(Subcommand, Debug, Clone)]
-pub enum SettingsCommands {
-    Discover {
-        #[arg(short, long, help = "Découvre les périphériques Freebox", default_value = None, action = ArgAction::SetTrue)]
-        all: Option<bool>,
+pub enum WifiCommands {
+    Status,
+    On,
+    Off,
+    Ap {
+        #[clap(subcommand)]
+        cmd: WifiApCommands,
     },
-    SetUrl {
-        url: String,
-        #[arg(short, long, help = "Test or not the provided url", default_value = None, action = ArgAction::SetTrue)]
-        bypass_test: Option<bool>,
+    Bss {
+        #[clap(subcommand)]
+        cmd: WifiBssCommands,
     },
-    GetUrl,
-    Reset {
-        #[arg(short, long, help = "Réinitialise les paramètres de l'application", default_value = None, action = ArgAction::SetTrue)]
-        confirm: bool,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum WifiApCommands {
+    List(ListArgs),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum WifiBssCommands {
+    List(WifiBssListArgs),
+    Set(WifiBssSetArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct WifiBssListArgs {
+    #[command(flatten)]
+    pub list: ListArgs,
+
+    #[arg(long, help = Message::HelpWifiShowKeys.to_string(), default_value = None, action = ArgAction::SetTrue)]
+    pub show_keys: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct WifiBssSetArgs {
+    #[arg(help = Message::HelpWifiBssId.to_string())]
+    pub id: String,
+
+    #[arg(short, long, help = Message::HelpWifiSsid.to_string())]
+    pub ssid: Option<String>,
+
+    #[arg(short, long, help = Message::HelpWifiKey.to_string())]
+    pub key: Option<String>,
+
+    #[arg(short, long, help = Message::HelpWifiEncryption.to_string(), value_enum)]
+    pub encryption: Option<WifiEncryption>,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ConnectionCommands {
+    RemoteApi {
+        #[clap(subcommand)]
+        cmd: ConnectionRemoteApiCommands,
     },
 }
 
 #[derive(Subcommand, Debug, Clone)]
-pub enum SystemCommands {
-    Shutdown,
-    Reboot,
+pub enum ConnectionRemoteApiCommands {
     Get,
+    Enable(ConnectionRemoteApiEnableArgs),
+    Disable,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ConnectionRemoteApiEnableArgs {
+    #[arg(long, help = Message::HelpHttpsCaFile.to_string())]
+    pub ca_file: Option<String>,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum LangCommands {
+    Get,
+    Set(LangSetArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct LangSetArgs {
+    #[arg(help = Message::HelpLang.to_string())]
+    pub lang: String,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum NotificationCommands {
+    Targets {
+        #[clap(subcommand)]
+        cmd: NotificationTargetsCommands,
+    },
+    Register(NotificationRegisterArgs),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum NotificationTargetsCommands {
+    List(ListArgs),
+    Rm(NotificationTargetsRmArgs),
+    UpdateSubscriptions(NotificationTargetsUpdateSubscriptionsArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct NotificationTargetsRmArgs {
+    #[arg(help = Message::HelpNotificationTargetId.to_string())]
+    pub id: Option<String>,
+
+    #[arg(long, help = Message::HelpNotificationUnusedSince.to_string(), conflicts_with = "id")]
+    pub unused_since: Option<i64>,
+
+    #[arg(short, long, help = Message::HelpConfirmYes.to_string(), default_value = None, action = ArgAction::SetTrue)]
+    pub yes: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct NotificationTargetsUpdateSubscriptionsArgs {
+    #[arg(help = Message::HelpNotificationTargetId.to_string())]
+    pub id: String,
+
+    #[arg(help = Message::HelpNotificationSubscriptions.to_string(), value_delimiter = ',')]
+    pub subscriptions: Vec<String>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct NotificationRegisterArgs {
+    #[arg(long, help = Message::HelpNotificationDeviceId.to_string())]
+    pub device_id: String,
+
+    #[arg(long, help = Message::HelpNotificationDeviceType.to_string())]
+    pub device_type: String,
+
+    #[arg(long, help = Message::HelpNotificationToken.to_string())]
+    pub token: String,
+
+    #[arg(long, help = Message::HelpNotificationDeviceName.to_string())]
+    pub device_name: Option<String>,
+
+    #[arg(long, help = Message::HelpNotificationBoxId.to_string())]
+    pub box_id: Option<String>,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum DhcpCommands {
+    Leases(DhcpLeasesArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct DhcpLeasesArgs {
+    #[arg(long = "static", help = Message::HelpDhcpStatic.to_string(), default_value = None, action = ArgAction::SetTrue)]
+    pub static_leases: bool,
+
+    #[command(flatten)]
+    pub list: ListArgs,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum RedirectionCommands {
+    List(ListArgs),
 }
@@ -1,83 +1,304 @@
+use crate::config::SecretError;
+use crate::i18n::Message;
+use crate::logging::{self, Verbosity};
+use reqwest::StatusCode;
+use serde::Deserialize;
 use std::error::Error;
 use url::ParseError;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ClientError {
     Timeout,
-    RequestError(&'static str),
-    UnknownError(&'static str),
-    Unauthorized(&'static str),
-    NotFound(&'static str),
+    RequestError(String),
+    UnknownError(String),
+    Unauthorized(String),
+    NotFound(String),
     BuilderError,
     InternalError,
-    InvalidUrl(&'static str),
-    CliNeedAuth(Option<&'static str>),
+    InvalidUrl(String),
+    CliNeedAuth(Option<String>),
+    CheckFailed(i32),
+    InvalidArgument(String),
+    DiscoveryFailed(String),
+    SecretStore(String),
+    PermissionDenied(String),
+    Unreachable,
+}
+
+/// Exit codes of the process, scripts rely on them so a code is never given
+/// another meaning. 1 (WARN) and 2 (CRIT) belong to the hardware check, which
+/// follows the Nagios plugin convention, so errors start at 3 (UNKNOWN for
+/// Nagios). Usage errors reported by clap exit with [`EXIT_INVALID_INPUT`].
+pub const EXIT_FAILURE: i32 = 3;
+pub const EXIT_INVALID_INPUT: i32 = 4;
+pub const EXIT_AUTH_REQUIRED: i32 = 5;
+pub const EXIT_NOT_FOUND: i32 = 6;
+pub const EXIT_TIMEOUT: i32 = 7;
+pub const EXIT_UNREACHABLE: i32 = 8;
+pub const EXIT_BOX_ERROR: i32 = 9;
+
+impl ClientError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ClientError::CheckFailed(code) => *code,
+            ClientError::InvalidArgument(_) | ClientError::InvalidUrl(_) => EXIT_INVALID_INPUT,
+            ClientError::Unauthorized(_)
+            | ClientError::CliNeedAuth(_)
+            | ClientError::PermissionDenied(_)
+            | ClientError::SecretStore(_) => EXIT_AUTH_REQUIRED,
+            ClientError::NotFound(_) => EXIT_NOT_FOUND,
+            ClientError::Timeout => EXIT_TIMEOUT,
+            ClientError::Unreachable | ClientError::DiscoveryFailed(_) => EXIT_UNREACHABLE,
+            ClientError::RequestError(_) => EXIT_BOX_ERROR,
+            ClientError::UnknownError(_)
+            | ClientError::BuilderError
+            | ClientError::InternalError => EXIT_FAILURE,
+        }
+    }
+
+    /// Message for people, printed on stderr before exiting.
+    pub fn message(&self) -> String {
+        let error = match self.exit_code() {
+            EXIT_INVALID_INPUT => Message::ErrorInvalidInput,
+            EXIT_AUTH_REQUIRED => Message::ErrorAuthRequired,
+            EXIT_NOT_FOUND => Message::ErrorNotFound,
+            EXIT_TIMEOUT => Message::ErrorTimeout,
+            EXIT_UNREACHABLE => Message::ErrorUnreachable,
+            EXIT_BOX_ERROR => Message::ErrorBox,
+            _ => Message::ErrorFailure,
+        };
+        let error = match self {
+            ClientError::PermissionDenied(_) => Message::ErrorPermissionDenied,
+            _ => error,
+        };
+        match self {
+            ClientError::RequestError(detail)
+            | ClientError::UnknownError(detail)
+            | ClientError::NotFound(detail)
+            | ClientError::Unauthorized(detail)
+            | ClientError::PermissionDenied(detail)
+            | ClientError::InvalidUrl(detail)
+            | ClientError::CliNeedAuth(Some(detail))
+            | ClientError::InvalidArgument(detail)
+            | ClientError::DiscoveryFailed(detail)
+            | ClientError::SecretStore(detail)
+                if !detail.is_empty() =>
+            {
+                Message::ErrorDetail(&error.to_string(), detail).to_string()
+            }
+            _ => error.to_string(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ApiError {
-    NotFound,
+    NotFound(String),
     Unauthorized(String),
     BadRequest(String),
     Internal(String),
     Forbidden(String),
     ParseBody,
+    Timeout,
+    Unreachable,
+}
+
+/// Body of the Freebox answers to failed requests.
+#[derive(Deserialize, Debug, Default)]
+struct ApiErrorBody {
+    #[serde(default)]
+    msg: String,
+    #[serde(default)]
+    error_code: String,
+}
+
+impl ApiError {
+    /// Error of a request answered with `status`, the Freebox sends an
+    /// `auth_required` or `invalid_token` code along with 403 for expired or
+    /// revoked tokens.
+    pub fn from_response(status: StatusCode, body: &[u8]) -> Self {
+        let body: ApiErrorBody = serde_json::from_slice(body).unwrap_or_default();
+        let message = match body.msg.is_empty() {
+            true => body.error_code.clone(),
+            false => body.msg,
+        };
+        match status.as_u16() {
+            400 => ApiError::BadRequest(message),
+            401 => ApiError::Unauthorized(message),
+            403 if matches!(body.error_code.as_str(), "auth_required" | "invalid_token") => {
+                ApiError::Unauthorized(message)
+            }
+            403 => ApiError::Forbidden(message),
+            404 => ApiError::NotFound(message),
+            _ => ApiError::Internal(message),
+        }
+    }
 }
 
 impl From<reqwest::Error> for ApiError {
     fn from(_value: reqwest::Error) -> Self {
-        println!("Error: {:?}", _value);
+        if _value.is_timeout() {
+            return ApiError::Timeout;
+        }
+        if _value.is_connect() {
+            return ApiError::Unreachable;
+        }
         match _value.source() {
             Some(source) => {
                 if source.to_string().contains("404") {
-                    ApiError::NotFound
+                    ApiError::NotFound(String::new())
                 } else if source.to_string().contains("401") {
-                    ApiError::Unauthorized("Unauthorized !".to_string())
+                    ApiError::Unauthorized(String::new())
                 } else if source.to_string().contains("400") {
-                    ApiError::BadRequest("Bad request !".to_string())
+                    ApiError::BadRequest(String::new())
                 } else if source.to_string().contains("403") {
-                    ApiError::Forbidden("Forbidden !".to_string())
+                    ApiError::Forbidden(String::new())
                 } else {
-                    ApiError::Internal("Internal error !".to_string())
+                    ApiError::Internal(String::new())
                 }
             }
-            None => ApiError::Internal("Internal error !".to_string()),
+            None => ApiError::Internal(String::new()),
         }
     }
 }
 
 impl From<ApiError> for ClientError {
+    /// Keeps the message of the box, or a generic one when it sent none.
     fn from(value: ApiError) -> Self {
+        let or = |message: String, default: Message| match message.is_empty() {
+            true => default.to_string(),
+            false => message,
+        };
         match value {
-            ApiError::NotFound => ClientError::NotFound("Not found !"),
-            ApiError::Unauthorized(_) => ClientError::Unauthorized("Unauthorized !"),
-            ApiError::BadRequest(_) => ClientError::RequestError("Bad request !"),
-            ApiError::Internal(_) => ClientError::InternalError,
-            ApiError::Forbidden(_) => ClientError::RequestError("Forbidden !"),
-            ApiError::ParseBody => ClientError::RequestError("Parse body error !"),
+            ApiError::NotFound(message) => {
+                ClientError::NotFound(or(message, Message::ErrorResourceNotFound))
+            }
+            ApiError::Unauthorized(message) => ClientError::Unauthorized(message),
+            ApiError::BadRequest(message) => {
+                ClientError::RequestError(or(message, Message::ErrorBadRequest))
+            }
+            ApiError::Internal(message) => {
+                ClientError::RequestError(or(message, Message::ErrorBoxInternal))
+            }
+            ApiError::Forbidden(message) => ClientError::PermissionDenied(message),
+            ApiError::ParseBody => ClientError::RequestError(Message::ErrorParseBody.to_string()),
+            ApiError::Timeout => ClientError::Timeout,
+            ApiError::Unreachable => ClientError::Unreachable,
         }
     }
 }
 
-impl Into<ClientError> for ParseError {
-    fn into(self) -> ClientError {
-        match self {
-            _ => ClientError::InvalidUrl("Url non valide !"),
-        }
+impl From<ParseError> for ClientError {
+    fn from(_: ParseError) -> Self {
+        ClientError::InvalidUrl(Message::ConfigInvalidUrl.to_string())
     }
 }
 
 impl From<reqwest::Error> for ClientError {
     fn from(error: reqwest::Error) -> Self {
-        println!("Error: {:?}", error);
+        logging::debug(Verbosity::Requests, format!("< {}", error));
         if error.is_builder() {
             ClientError::BuilderError
         } else if error.is_timeout() {
             ClientError::Timeout
+        } else if error.is_connect() {
+            ClientError::Unreachable
         } else if error.is_request() {
-            ClientError::RequestError("Request error !")
+            ClientError::RequestError(Message::ErrorRequestFailed.to_string())
         } else {
-            ClientError::UnknownError("Unknown error !")
+            ClientError::UnknownError(String::new())
         }
     }
 }
+
+impl From<SecretError> for ClientError {
+    fn from(error: SecretError) -> Self {
+        let detail = match error {
+            SecretError::Unavailable(_) => Message::ErrorSecretStoreUnavailable,
+            SecretError::InvalidPassphrase => Message::ErrorInvalidPassphrase,
+            SecretError::Corrupted => Message::ErrorSecretStoreCorrupted,
+        };
+        ClientError::SecretStore(detail.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(status: u16, body: &str) -> ApiError {
+        ApiError::from_response(StatusCode::from_u16(status).unwrap(), body.as_bytes())
+    }
+
+    #[test]
+    fn expired_sessions_are_unauthorized() {
+        let body =
+            r#"{"success":false,"error_code":"auth_required","msg":"Vous devez vous connecter"}"#;
+        assert_eq!(
+            error(403, body),
+            ApiError::Unauthorized("Vous devez vous connecter".to_string())
+        );
+        let body = r#"{"success":false,"error_code":"invalid_token"}"#;
+        assert_eq!(
+            error(403, body),
+            ApiError::Unauthorized("invalid_token".to_string())
+        );
+    }
+
+    #[test]
+    fn missing_rights_are_forbidden() {
+        let body = r#"{"success":false,"error_code":"insufficient_rights","msg":"Accès refusé"}"#;
+        assert_eq!(
+            error(403, body),
+            ApiError::Forbidden("Accès refusé".to_string())
+        );
+    }
+
+    #[test]
+    fn unexpected_bodies_are_kept_by_status() {
+        assert_eq!(error(404, "<html>"), ApiError::NotFound(String::new()));
+        assert_eq!(error(400, ""), ApiError::BadRequest(String::new()));
+        assert_eq!(error(502, "<html>"), ApiError::Internal(String::new()));
+    }
+
+    #[test]
+    fn exit_codes_follow_the_error_kind() {
+        let code = |error: ApiError| ClientError::from(error).exit_code();
+        assert_eq!(code(ApiError::BadRequest(String::new())), EXIT_BOX_ERROR);
+        assert_eq!(
+            code(ApiError::Unauthorized(String::new())),
+            EXIT_AUTH_REQUIRED
+        );
+        assert_eq!(code(ApiError::Forbidden(String::new())), EXIT_AUTH_REQUIRED);
+        assert!(![1, 2].contains(&EXIT_FAILURE) && ![1, 2].contains(&EXIT_INVALID_INPUT));
+        assert_eq!(code(ApiError::NotFound(String::new())), EXIT_NOT_FOUND);
+        assert_eq!(code(ApiError::Timeout), EXIT_TIMEOUT);
+        assert_eq!(code(ApiError::Unreachable), EXIT_UNREACHABLE);
+        assert_eq!(ClientError::CheckFailed(9).exit_code(), 9);
+        assert_eq!(
+            ClientError::InvalidArgument(String::new()).exit_code(),
+            EXIT_INVALID_INPUT
+        );
+    }
+
+    #[test]
+    fn the_box_message_is_printed() {
+        let body = r#"{"success":false,"error_code":"insufficient_rights","msg":"Accès refusé"}"#;
+        let forbidden = ClientError::from(error(403, body));
+        assert_eq!(
+            forbidden,
+            ClientError::PermissionDenied("Accès refusé".to_string())
+        );
+        assert!(forbidden.message().ends_with("Accès refusé"));
+        let body = r#"{"success":false,"error_code":"noent","msg":"Aucune règle avec cet id"}"#;
+        assert_eq!(
+            ClientError::from(error(404, body)),
+            ClientError::NotFound("Aucune règle avec cet id".to_string())
+        );
+        let missing = ClientError::from(error(400, "<html>"));
+        assert_eq!(
+            missing,
+            ClientError::RequestError(Message::ErrorBadRequest.to_string())
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::app::ResponseResult;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -56,11 +57,19 @@ pub struct AirMediaConfig {
     pub password: String,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AirMediaConfigBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AirMediaReceiver {
     pub name: String,
     pub password_protected: bool,
-    pub capabilities: Vec<AirMediaReceiverCapabilities>,
+    pub capabilities: AirMediaReceiverCapabilities,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -70,3 +79,51 @@ pub struct AirMediaReceiverCapabilities {
     pub video: bool,
     pub screen: bool,
 }
+
+impl AirMediaReceiverCapabilities {
+    pub fn supports(&self, media_type: &AirMediaMediaType) -> bool {
+        match media_type {
+            AirMediaMediaType::Photo => self.photo,
+            AirMediaMediaType::Audio => self.audio,
+            AirMediaMediaType::Video => self.video,
+            AirMediaMediaType::Screen => self.screen,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum AirMediaAction {
+    #[serde(rename = "start")]
+    Start,
+    #[serde(rename = "stop")]
+    Stop,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, clap::ValueEnum)]
+pub enum AirMediaMediaType {
+    #[serde(rename = "photo")]
+    Photo,
+    #[serde(rename = "audio")]
+    Audio,
+    #[serde(rename = "video")]
+    Video,
+    #[serde(rename = "screen")]
+    Screen,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AirMediaReceiverRequest {
+    pub action: AirMediaAction,
+    pub media_type: AirMediaMediaType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+}
+
+pub type GetAirMediaConfigResponse = ResponseResult<AirMediaConfig>;
+pub type UpdateAirMediaConfigResponse = ResponseResult<AirMediaConfig>;
+pub type ListAirMediaReceiversResponse = ResponseResult<Vec<AirMediaReceiver>>;
+pub type AirMediaReceiverRequestResponse = ResponseResult<Option<()>>;
@@ -51,7 +51,6 @@ pub enum AuthTrackAuthorizationProgressStatus {
 #[derive(Deserialize, Debug, Clone)]
 pub struct AuthTrackAuthorizationProgressResult {
     pub status: AuthTrackAuthorizationProgressStatus,
-    pub challenge: String,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -70,10 +69,14 @@ pub struct AuthSessionStartRequest {
 }
 
 impl AuthSessionStartRequest {
-    pub fn new(app_token: String, challenge: String) -> Option<Self> {
+    pub fn new(app_id: String, app_token: String, challenge: String) -> Option<Self> {
         let auth_token = AuthTokenRequest::default();
         Some(AuthSessionStartRequest {
-            app_id: auth_token.app_id,
+            app_id: if app_id.is_empty() {
+                auth_token.app_id
+            } else {
+                app_id
+            },
             app_version: auth_token.app_version,
             password: Self::generate_password(app_token, challenge)?,
         })
@@ -104,7 +107,7 @@ impl AuthSessionStartRequest {
     }
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct AuthSessionPermissions {
     pub settings: Option<bool>,
     pub contacts: Option<bool>,
@@ -114,12 +117,92 @@ pub struct AuthSessionPermissions {
     pub parental: Option<bool>,
     pub pvr: Option<bool>,
     pub profile: Option<bool>,
+    pub camera: Option<bool>,
+    pub home: Option<bool>,
+    pub player: Option<bool>,
+    pub tv: Option<bool>,
+    pub vm: Option<bool>,
+}
+
+impl AuthSessionPermissions {
+    pub fn has(&self, permission: Permission) -> bool {
+        let granted = match permission {
+            Permission::Settings => self.settings,
+            Permission::Contacts => self.contacts,
+            Permission::Calls => self.calls,
+            Permission::Explorer => self.explorer,
+            Permission::Downloader => self.downloader,
+            Permission::Parental => self.parental,
+            Permission::Pvr => self.pvr,
+            Permission::Profile => self.profile,
+            Permission::Camera => self.camera,
+            Permission::Home => self.home,
+            Permission::Player => self.player,
+            Permission::Tv => self.tv,
+            Permission::Vm => self.vm,
+        };
+        granted.unwrap_or(false)
+    }
+}
+
+/// Access right granted to the app in Freebox OS > Gestion des accès.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Permission {
+    Settings,
+    Contacts,
+    Calls,
+    Explorer,
+    Downloader,
+    Parental,
+    Pvr,
+    Profile,
+    Camera,
+    Home,
+    Player,
+    Tv,
+    Vm,
+}
+
+impl Permission {
+    pub const ALL: [Permission; 13] = [
+        Permission::Settings,
+        Permission::Contacts,
+        Permission::Calls,
+        Permission::Explorer,
+        Permission::Downloader,
+        Permission::Parental,
+        Permission::Pvr,
+        Permission::Profile,
+        Permission::Camera,
+        Permission::Home,
+        Permission::Player,
+        Permission::Tv,
+        Permission::Vm,
+    ];
+
+    /// Name of the permission in the api.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Permission::Settings => "settings",
+            Permission::Contacts => "contacts",
+            Permission::Calls => "calls",
+            Permission::Explorer => "explorer",
+            Permission::Downloader => "downloader",
+            Permission::Parental => "parental",
+            Permission::Pvr => "pvr",
+            Permission::Profile => "profile",
+            Permission::Camera => "camera",
+            Permission::Home => "home",
+            Permission::Player => "player",
+            Permission::Tv => "tv",
+            Permission::Vm => "vm",
+        }
+    }
 }
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct AuthSessionStartResult {
     pub session_token: String,
-    pub challenge: String,
     pub permissions: AuthSessionPermissions,
 }
 
@@ -135,8 +218,6 @@ pub struct AuthSessionStartResponse {
 pub struct AuthLoginResult {
     pub logged_in: bool,
     pub challenge: Option<String>,
-    pub password_salt: String,
-    pub password_set: bool,
 }
 
 #[derive(Deserialize, Debug, Clone)]
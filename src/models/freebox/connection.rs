@@ -103,15 +103,40 @@ pub struct ConnectionConfiguration {
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ConnectionConfigurationBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub ping: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub remote_access: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub remote_access_port: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_remote_access: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub wol: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub adblock: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub allow_token_request: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub sip_alg: Option<ConnectionConfigurationSigAlg>,
 }
 
+impl ConnectionConfigurationBody {
+    /// Turns remote access on, with the API reachable from the internet.
+    pub fn remote_api(enabled: bool) -> Self {
+        ConnectionConfigurationBody {
+            ping: None,
+            remote_access: enabled.then_some(true),
+            remote_access_port: None,
+            api_remote_access: Some(enabled),
+            wol: None,
+            adblock: None,
+            allow_token_request: None,
+            sip_alg: None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct FTTHStatus {
     pub sfp_present: bool,
@@ -182,6 +207,7 @@ pub enum DDNSStatusKind {
 
 pub type GetConnectionStatus = ResponseResult<ConnectionStatus>;
 pub type GetConnectionConfiguration = ResponseResult<ConnectionConfiguration>;
+pub type UpdateConnectionConfiguration = ResponseResult<ConnectionConfiguration>;
 pub type GetFTTHStatus = ResponseResult<FTTHStatus>;
 pub type GetDDNSConfig = ResponseResult<DDNSConfig>;
 pub type UpdateDDNSConfig = ResponseResult<DDNSConfig>;
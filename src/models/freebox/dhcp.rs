@@ -59,7 +59,7 @@ pub struct DHCPDynamicLease {
     //TODO: pub host: LanHost
 }
 
-pub type ListDHCPStaticLeasesResponse = ResponseResult<Vec<DHCPStaticLease>>;
-pub type ListDHCPDynamicLeasesResponse = ResponseResult<Vec<DHCPDynamicLease>>;
+pub type ListDHCPStaticLeasesResponse = ResponseResult<Option<Vec<DHCPStaticLease>>>;
+pub type ListDHCPDynamicLeasesResponse = ResponseResult<Option<Vec<DHCPDynamicLease>>>;
 pub type GetDHCPStaticLeasesResponse = ResponseResult<DHCPStaticLease>;
 pub type GetDHCPDynamicLeasesResponse = ResponseResult<DHCPDynamicLease>;
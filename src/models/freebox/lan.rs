@@ -1,5 +1,6 @@
 use crate::app::ResponseResult;
 use serde::{Deserialize, Serialize};
+use std::fmt::Display;
 
 #[derive(Debug, Clone, Deserialize)]
 pub enum LanConfigError {
@@ -70,21 +71,41 @@ pub struct LanCount {
 pub struct LanHost {
     pub id: String,
     pub primary_name: String,
-    pub l2ident: Vec<LanHostL2Ident>,
+    pub l2ident: LanHostL2Ident,
     pub host_type: LanHostType,
+    #[serde(default)]
     pub primary_name_manual: bool,
+    #[serde(default)]
     pub vendor_name: String,
     pub persistent: bool,
     pub reachable: bool,
+    #[serde(default)]
     pub last_time_reachable: i32,
     pub active: bool,
+    #[serde(default)]
     pub last_activity: i32,
+    #[serde(default)]
     pub first_activity: i32,
+    #[serde(default)]
     pub names: Vec<LanHostName>,
+    #[serde(default)]
     pub l3connectivities: Vec<LanHostL3Connectivity>,
     pub network_control: Option<LanHostNetworkControl>,
 }
 
+impl LanHost {
+    /// IPv4 address of the host, the active one first.
+    pub fn ipv4(&self) -> Option<&str> {
+        let mut addresses = self
+            .l3connectivities
+            .iter()
+            .filter(|l3| matches!(l3.af, LanHostL3ConnectivityAf::IPv4))
+            .collect::<Vec<&LanHostL3Connectivity>>();
+        addresses.sort_by_key(|l3| !l3.active);
+        addresses.first().map(|l3| l3.addr.as_str())
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct LanHostName {
     pub name: String,
@@ -94,8 +115,9 @@ pub struct LanHostName {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct LanHostL2Ident {
     pub id: String,
+    /// `mac_address` for every host seen on the LAN so far.
     #[serde(rename = "type")]
-    pub kind: LanHostL2IdentType,
+    pub kind: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -184,10 +206,40 @@ pub enum LanHostType {
     MultimediaDevice,
     #[serde(rename = "car")]
     Car,
-    #[serde(rename = "other")]
+    #[serde(rename = "other", other)]
     Other,
 }
 
+impl Display for LanHostType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            LanHostType::Workstation => "workstation",
+            LanHostType::Laptop => "laptop",
+            LanHostType::Smartphone => "smartphone",
+            LanHostType::Tablet => "tablet",
+            LanHostType::Printer => "printer",
+            LanHostType::Console => "vg_console",
+            LanHostType::Tv => "tv",
+            LanHostType::Nas => "nas",
+            LanHostType::CameraIp => "ip_camera",
+            LanHostType::PhoneIp => "ip_phone",
+            LanHostType::FreeboxPlayer => "freebox_player",
+            LanHostType::FreeboxHD => "freebox_hd",
+            LanHostType::FreeboxCrystal => "freebox_crystal",
+            LanHostType::FreeboxMini => "freebox_mini",
+            LanHostType::FreeboxDelta => "freebox_delta",
+            LanHostType::FreeboxWifi => "freebox_wifi",
+            LanHostType::FreeboxOne => "freebox_one",
+            LanHostType::FreeboxWifiPop => "freebox_pop",
+            LanHostType::NetworkDevice => "networking_device",
+            LanHostType::MultimediaDevice => "multimedia_device",
+            LanHostType::Car => "car",
+            LanHostType::Other => "other",
+        };
+        write!(f, "{}", str)
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct WakeOnLan {
     pub mac: String,
@@ -195,7 +247,8 @@ pub struct WakeOnLan {
 }
 
 pub type ListLanCountResponse = ResponseResult<Vec<LanCount>>;
-pub type ListLanResponse = ResponseResult<Vec<LanHost>>;
+/// `result` is left out by the box when no host is known.
+pub type ListLanResponse = ResponseResult<Option<Vec<LanHost>>>;
 pub type GetLanResponse = ResponseResult<LanHost>;
 pub type UpdateLanResponse = ResponseResult<LanHost>;
 
@@ -1,7 +1,7 @@
 use crate::app::ResponseResult;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct LcdInfoResult {
     pub brightness: i32,
     pub orientation_forced: bool,
@@ -21,10 +21,13 @@ pub enum LcdError {
     NotSupported,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct LcdUpdateBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub brightness: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub orientation: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub hide_wifi_key: Option<bool>,
 }
 
@@ -1,3 +1,4 @@
+use crate::app::ResponseResult;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -11,7 +12,8 @@ pub enum NotificationError {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct NotificationTarget {
     pub id: String,
-    pub last_use: i32,
+    pub last_use: i64,
+    #[serde(rename = "type")]
     pub kind: String,
     pub name: String,
     pub api_url: String,
@@ -27,3 +29,13 @@ pub struct NotificationSend {
     pub device_name: String,
     pub device_id: String,
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NotificationTargetUpdateBody {
+    pub subscriptions: Vec<String>,
+}
+
+pub type ListNotificationTargetsResponse = ResponseResult<Option<Vec<NotificationTarget>>>;
+pub type UpdateNotificationTargetResponse = ResponseResult<NotificationTarget>;
+pub type DeleteNotificationTargetResponse = ResponseResult<Option<()>>;
+pub type RegisterNotificationTargetResponse = ResponseResult<NotificationTarget>;
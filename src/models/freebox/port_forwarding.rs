@@ -1,18 +1,21 @@
 use crate::app::ResponseResult;
 use serde::{Deserialize, Serialize};
+use std::fmt::Display;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PortForwardingConfig {
     pub id: i32,
     pub enabled: bool,
     pub ip_proto: IpProtocol,
-    pub wan_port_start: String,
+    pub wan_port_start: i32,
     pub wan_port_end: i32,
     pub lan_ip: String,
     pub lan_port: i32,
+    #[serde(default)]
     pub hostname: String,
     //TODO: pub host: LanHost,
     pub src_ip: String,
+    #[serde(default)]
     pub comment: String,
 }
 
@@ -49,6 +52,16 @@ pub enum IpProtocol {
     Udp,
 }
 
-pub type ListPortForwardingResponse = ResponseResult<Vec<PortForwardingConfig>>;
+impl Display for IpProtocol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            IpProtocol::Tcp => "tcp",
+            IpProtocol::Udp => "udp",
+        };
+        write!(f, "{}", str)
+    }
+}
+
+pub type ListPortForwardingResponse = ResponseResult<Option<Vec<PortForwardingConfig>>>;
 pub type GetPortForwardingResponse = ResponseResult<PortForwardingConfig>;
 pub type UpdatePortForwardingResponse = ResponseResult<PortForwardingConfig>;
@@ -1,9 +1,15 @@
 use crate::app::ResponseResult;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
 
 pub type SystemInfoResult = ResponseResult<SystemInfo>;
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Deserialize, Debug)]
+pub struct SystemPowerResponse {
+    pub success: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SystemInfo {
     pub mac: String,
     pub sensors: Vec<SystemSensor>,
@@ -20,14 +26,14 @@ pub struct SystemInfo {
     pub firmware_version: String,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SystemSensor {
     pub id: String,
     pub name: String,
     pub value: i32,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SystemModel {
     pub pretty_name: String,
     pub name: SystemModelType,
@@ -44,7 +50,7 @@ pub struct SystemModel {
     pub has_wop: Option<bool>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum SystemModelType {
     #[serde(rename = "fbxgw-r1")]
     FreeboxServerV6R1,
@@ -66,7 +72,7 @@ pub enum SystemModelType {
     FreeboxV9R1,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SystemExpansion {
     #[serde(rename = "type")]
     pub kind: String,
@@ -77,7 +83,7 @@ pub struct SystemExpansion {
     pub bundle: String,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum DiskStatus {
     #[serde(rename = "not_detected")]
     NotDetected,
@@ -90,3 +96,16 @@ pub enum DiskStatus {
     #[serde(rename = "active")]
     Active,
 }
+
+impl Display for DiskStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            DiskStatus::NotDetected => "not_detected",
+            DiskStatus::Disabled => "disabled",
+            DiskStatus::Initializing => "initializing",
+            DiskStatus::Error => "error",
+            DiskStatus::Active => "active",
+        };
+        write!(f, "{}", str)
+    }
+}
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 pub struct VersionResponse {
     pub uid: String,
     pub https_available: bool,
@@ -9,6 +9,10 @@ pub struct VersionResponse {
     pub api_version: String,
     pub api_base_url: String,
     pub https_port: i32,
+    #[serde(default)]
+    pub device_type: String,
+    #[serde(default)]
+    pub box_model_name: String,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
@@ -25,40 +29,30 @@ pub enum FreeboxMajorVersion {
     V9,
     V10,
     V11,
+    /// Major reported by a box newer than the versions above, kept as is.
+    Other(u32),
 }
 
 impl FreeboxMajorVersion {
-    pub fn from_api_version(version: String) -> Self {
-        let b = version.split(".").collect::<Vec<&str>>();
-        if b.len() > 1 {
-            return FreeboxMajorVersion::from(b[0]);
-        }
-        FreeboxMajorVersion::V8
-    }
-}
-
-impl From<&str> for FreeboxMajorVersion {
-    fn from(value: &str) -> Self {
-        FreeboxMajorVersion::from(String::from(value))
-    }
-}
-
-impl From<String> for FreeboxMajorVersion {
-    fn from(value: String) -> Self {
-        match value {
-            v if v == "v1" => FreeboxMajorVersion::V1,
-            v if v == "v2" => FreeboxMajorVersion::V2,
-            v if v == "v3" => FreeboxMajorVersion::V3,
-            v if v == "v4" => FreeboxMajorVersion::V4,
-            v if v == "v5" => FreeboxMajorVersion::V5,
-            v if v == "v6" => FreeboxMajorVersion::V6,
-            v if v == "v7" => FreeboxMajorVersion::V7,
-            v if v == "v8" => FreeboxMajorVersion::V8,
-            v if v == "v9" => FreeboxMajorVersion::V9,
-            v if v == "v10" => FreeboxMajorVersion::V10,
-            v if v == "v11" => FreeboxMajorVersion::V11,
-            _ => FreeboxMajorVersion::V11,
-        }
+    /// Major version of a full api version as returned by `/api_version`, e.g.
+    /// `8.0` gives `v8`, `None` when the version has no valid major.
+    pub fn from_api_version(version: &str) -> Option<Self> {
+        let major: u32 = version.split('.').next()?.parse().ok()?;
+        Some(match major {
+            0 => return None,
+            1 => FreeboxMajorVersion::V1,
+            2 => FreeboxMajorVersion::V2,
+            3 => FreeboxMajorVersion::V3,
+            4 => FreeboxMajorVersion::V4,
+            5 => FreeboxMajorVersion::V5,
+            6 => FreeboxMajorVersion::V6,
+            7 => FreeboxMajorVersion::V7,
+            8 => FreeboxMajorVersion::V8,
+            9 => FreeboxMajorVersion::V9,
+            10 => FreeboxMajorVersion::V10,
+            11 => FreeboxMajorVersion::V11,
+            major => FreeboxMajorVersion::Other(major),
+        })
     }
 }
 
@@ -76,7 +70,26 @@ impl Display for FreeboxMajorVersion {
             FreeboxMajorVersion::V9 => "v9".to_string(),
             FreeboxMajorVersion::V10 => "v10".to_string(),
             FreeboxMajorVersion::V11 => "v11".to_string(),
+            FreeboxMajorVersion::Other(major) => format!("v{}", major),
         };
         write!(f, "{}", str)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::FreeboxMajorVersion;
+
+    #[test]
+    fn major_of_the_api_version() {
+        let major = |version| FreeboxMajorVersion::from_api_version(version);
+
+        assert_eq!(major("8.0"), Some(FreeboxMajorVersion::V8));
+        assert_eq!(major("11.1"), Some(FreeboxMajorVersion::V11));
+        assert_eq!(major("12.0"), Some(FreeboxMajorVersion::Other(12)));
+        assert_eq!(major("12.0").unwrap().to_string(), "v12");
+        for invalid in ["0.9", "", "v8", "huit"] {
+            assert_eq!(major(invalid), None, "{}", invalid);
+        }
+    }
+}
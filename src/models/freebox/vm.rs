@@ -1,4 +1,6 @@
+use crate::app::ResponseResult;
 use serde::{Deserialize, Serialize};
+use std::fmt::Display;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum VmError {
@@ -67,7 +69,9 @@ pub struct Vm {
     pub enable_screen: bool,
     //TODO: pub bind_usb_ports: Vec<VmSystemInfo>,
     pub enable_cloudinit: bool,
+    #[serde(default)]
     pub cloudinit_hostname: String,
+    #[serde(default)]
     pub cloudinit_userdata: String,
     pub mac: String,
     pub os: String,
@@ -103,6 +107,18 @@ pub enum VmStatus {
     Stopping,
 }
 
+impl Display for VmStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            VmStatus::Stopped => "stopped",
+            VmStatus::Running => "running",
+            VmStatus::Starting => "starting",
+            VmStatus::Stopping => "stopping",
+        };
+        write!(f, "{}", str)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct VmDistribution {
     pub name: String,
@@ -135,3 +151,6 @@ pub enum VmDiskTaskType {
     #[serde(rename = "resize")]
     Resize,
 }
+
+/// `result` is left out by the box when there is no VM.
+pub type ListVmResponse = ResponseResult<Option<Vec<Vm>>>;
@@ -1 +1,306 @@
+use crate::app::ResponseResult;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WifiGlobalConfig {
+    pub enabled: bool,
+    pub mac_filter_state: WifiMacFilterState,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WifiGlobalConfigBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum WifiMacFilterState {
+    #[serde(rename = "disabled")]
+    Disabled,
+    #[serde(rename = "whitelist")]
+    Whitelist,
+    #[serde(rename = "blacklist")]
+    Blacklist,
+    #[serde(rename = "unknown", other)]
+    Unknown,
+}
+
+impl Display for WifiMacFilterState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            WifiMacFilterState::Disabled => "disabled",
+            WifiMacFilterState::Whitelist => "whitelist",
+            WifiMacFilterState::Blacklist => "blacklist",
+            WifiMacFilterState::Unknown => "unknown",
+        };
+        write!(f, "{}", str)
+    }
+}
+
+/// Radio of the box, one for each band.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WifiAp {
+    pub id: i32,
+    pub name: String,
+    pub status: WifiApStatus,
+    pub config: WifiApConfig,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WifiApStatus {
+    pub state: WifiApState,
+    pub channel_width: String,
+    pub primary_channel: i32,
+    pub secondary_channel: i32,
+    #[serde(default)]
+    pub dfs_cac_remaining_time: i32,
+}
+
+/// Configured channels, 0 lets the box pick one.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WifiApConfig {
+    pub band: WifiBand,
+    pub channel_width: String,
+    pub primary_channel: i32,
+    pub secondary_channel: i32,
+    #[serde(default)]
+    pub dfs_enabled: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum WifiApState {
+    #[serde(rename = "scanning")]
+    Scanning,
+    #[serde(rename = "no_param")]
+    NoParam,
+    #[serde(rename = "bad_param")]
+    BadParam,
+    #[serde(rename = "disabled")]
+    Disabled,
+    #[serde(rename = "disabled_planning")]
+    DisabledPlanning,
+    #[serde(rename = "no_active_bss")]
+    NoActiveBss,
+    #[serde(rename = "starting")]
+    Starting,
+    #[serde(rename = "acs")]
+    Acs,
+    #[serde(rename = "ht_scan")]
+    HtScan,
+    #[serde(rename = "dfs")]
+    Dfs,
+    #[serde(rename = "active")]
+    Active,
+    #[serde(rename = "failed")]
+    Failed,
+    #[serde(rename = "unknown", other)]
+    Unknown,
+}
+
+impl Display for WifiApState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            WifiApState::Scanning => "scanning",
+            WifiApState::NoParam => "no_param",
+            WifiApState::BadParam => "bad_param",
+            WifiApState::Disabled => "disabled",
+            WifiApState::DisabledPlanning => "disabled_planning",
+            WifiApState::NoActiveBss => "no_active_bss",
+            WifiApState::Starting => "starting",
+            WifiApState::Acs => "acs",
+            WifiApState::HtScan => "ht_scan",
+            WifiApState::Dfs => "dfs",
+            WifiApState::Active => "active",
+            WifiApState::Failed => "failed",
+            WifiApState::Unknown => "unknown",
+        };
+        write!(f, "{}", str)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum WifiBand {
+    #[serde(rename = "2d4g")]
+    Band2d4g,
+    #[serde(rename = "5g")]
+    Band5g,
+    #[serde(rename = "6g")]
+    Band6g,
+    #[serde(rename = "60g")]
+    Band60g,
+    #[serde(rename = "unknown", other)]
+    Unknown,
+}
+
+impl Display for WifiBand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            WifiBand::Band2d4g => "2.4 GHz",
+            WifiBand::Band5g => "5 GHz",
+            WifiBand::Band6g => "6 GHz",
+            WifiBand::Band60g => "60 GHz",
+            WifiBand::Unknown => "unknown",
+        };
+        write!(f, "{}", str)
+    }
+}
+
+/// Network broadcast by an access point, identified by its MAC address.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WifiBss {
+    pub id: String,
+    pub phy_id: i32,
+    pub status: WifiBssStatus,
+    pub config: WifiBssConfig,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WifiBssStatus {
+    pub state: WifiBssState,
+    pub sta_count: i32,
+    pub authorized_sta_count: i32,
+    pub is_main_bss: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WifiBssConfig {
+    pub enabled: bool,
+    pub use_default_config: bool,
+    pub ssid: String,
+    pub hide_ssid: bool,
+    pub encryption: WifiEncryption,
+    pub key: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum WifiBssState {
+    #[serde(rename = "phy_stopped")]
+    PhyStopped,
+    #[serde(rename = "no_param")]
+    NoParam,
+    #[serde(rename = "bad_param")]
+    BadParam,
+    #[serde(rename = "disabled")]
+    Disabled,
+    #[serde(rename = "starting")]
+    Starting,
+    #[serde(rename = "active")]
+    Active,
+    #[serde(rename = "failed")]
+    Failed,
+    #[serde(rename = "unknown", other)]
+    Unknown,
+}
+
+impl Display for WifiBssState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            WifiBssState::PhyStopped => "phy_stopped",
+            WifiBssState::NoParam => "no_param",
+            WifiBssState::BadParam => "bad_param",
+            WifiBssState::Disabled => "disabled",
+            WifiBssState::Starting => "starting",
+            WifiBssState::Active => "active",
+            WifiBssState::Failed => "failed",
+            WifiBssState::Unknown => "unknown",
+        };
+        write!(f, "{}", str)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, clap::ValueEnum)]
+pub enum WifiEncryption {
+    #[serde(rename = "wep")]
+    #[value(name = "wep")]
+    Wep,
+    #[serde(rename = "wpa_psk_auto")]
+    #[value(name = "wpa_psk_auto")]
+    WpaPskAuto,
+    #[serde(rename = "wpa_psk_tkip")]
+    #[value(name = "wpa_psk_tkip")]
+    WpaPskTkip,
+    #[serde(rename = "wpa_psk_ccmp")]
+    #[value(name = "wpa_psk_ccmp")]
+    WpaPskCcmp,
+    #[serde(rename = "wpa12_psk_auto")]
+    #[value(name = "wpa12_psk_auto")]
+    Wpa12PskAuto,
+    #[serde(rename = "wpa2_psk_auto")]
+    #[value(name = "wpa2_psk_auto")]
+    Wpa2PskAuto,
+    #[serde(rename = "wpa2_psk_tkip")]
+    #[value(name = "wpa2_psk_tkip")]
+    Wpa2PskTkip,
+    #[serde(rename = "wpa2_psk_ccmp")]
+    #[value(name = "wpa2_psk_ccmp")]
+    Wpa2PskCcmp,
+    #[serde(rename = "wpa23_psk_ccmp")]
+    #[value(name = "wpa23_psk_ccmp")]
+    Wpa23PskCcmp,
+    /// Reported by a newer firmware, it can't be set.
+    #[serde(rename = "unknown", other)]
+    #[value(skip)]
+    Unknown,
+}
+
+impl WifiEncryption {
+    /// WEP keys are 5 or 13 characters (10 or 26 hexadecimal digits), WPA
+    /// passphrases 8 to 63 printable characters or 64 hexadecimal digits.
+    /// Keys of an unknown encryption are left to the box.
+    pub fn accepts_key(&self, key: &str) -> bool {
+        let hexadecimal = key.chars().all(|c| c.is_ascii_hexdigit());
+        match self {
+            WifiEncryption::Wep => match key.len() {
+                5 | 13 => key.is_ascii(),
+                10 | 26 => hexadecimal,
+                _ => false,
+            },
+            WifiEncryption::Unknown => true,
+            _ => match key.len() {
+                8..=63 => key.chars().all(|c| c.is_ascii() && !c.is_ascii_control()),
+                64 => hexadecimal,
+                _ => false,
+            },
+        }
+    }
+}
+
+impl Display for WifiEncryption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            WifiEncryption::Wep => "WEP",
+            WifiEncryption::WpaPskAuto => "WPA (TKIP/AES)",
+            WifiEncryption::WpaPskTkip => "WPA (TKIP)",
+            WifiEncryption::WpaPskCcmp => "WPA (AES)",
+            WifiEncryption::Wpa12PskAuto => "WPA/WPA2 (TKIP/AES)",
+            WifiEncryption::Wpa2PskAuto => "WPA2 (TKIP/AES)",
+            WifiEncryption::Wpa2PskTkip => "WPA2 (TKIP)",
+            WifiEncryption::Wpa2PskCcmp => "WPA2 (AES)",
+            WifiEncryption::Wpa23PskCcmp => "WPA2/WPA3 (AES)",
+            WifiEncryption::Unknown => "unknown",
+        };
+        write!(f, "{}", str)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WifiBssUpdateBody {
+    pub config: WifiBssConfigBody,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct WifiBssConfigBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ssid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<WifiEncryption>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+}
+
+pub type GetWifiGlobalConfigResponse = ResponseResult<WifiGlobalConfig>;
+pub type UpdateWifiGlobalConfigResponse = ResponseResult<WifiGlobalConfig>;
+pub type ListWifiApResponse = ResponseResult<Option<Vec<WifiAp>>>;
+pub type ListWifiBssResponse = ResponseResult<Option<Vec<WifiBss>>>;
+pub type UpdateWifiBssResponse = ResponseResult<WifiBss>;
@@ -0,0 +1,466 @@
+use crate::handlers::{new_headless_table, new_table};
+use crate::i18n::Message;
+use crate::models::args::ListArgs;
+use crate::models::exception::ClientError;
+use clap::ValueEnum;
+use comfy_table::{Cell, Table};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
+
+/// Format of the results printed on stdout, chosen with `--output`.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+    Yaml,
+    Csv,
+}
+
+impl OutputFormat {
+    /// Tables are meant for people, every other format for another program,
+    /// so interactive prompts are skipped for them.
+    pub fn is_human(&self) -> bool {
+        *self == OutputFormat::Table
+    }
+}
+
+/// Prints a list of items, filtered, sorted and reduced to some columns as
+/// asked on the command line. `table` builds the human readable table, it is
+/// only called for the table format when no column is selected.
+pub fn list<T: Serialize + Clone>(
+    format: OutputFormat,
+    args: &ListArgs,
+    items: &[T],
+    table: impl FnOnce(&[T]) -> Table,
+) -> Result<(), ClientError> {
+    let rows = rows(items)?;
+    let fields = fields(&rows);
+    let mut selected: Vec<usize> = (0..rows.len()).collect();
+    for filter in &args.filter {
+        let filter = Filter::parse(filter, &fields)?;
+        selected.retain(|&index| filter.matches(&rows[index]));
+    }
+    if let Some(sort_by) = &args.sort_by {
+        let field = resolve_one(sort_by, &fields)?;
+        selected.sort_by(|&a, &b| compare(rows[a].get(&field), rows[b].get(&field)));
+    }
+    let mut columns = vec![];
+    for column in &args.columns {
+        match resolve(column, &fields).as_slice() {
+            [] => return Err(unknown_field(column, &fields)),
+            resolved => columns.extend_from_slice(resolved),
+        }
+    }
+    let selected_rows = selected.iter().map(|&index| &rows[index]);
+    match (format, columns.is_empty()) {
+        (OutputFormat::Table, true) => {
+            let items: Vec<T> = selected.iter().map(|&index| items[index].clone()).collect();
+            let table = table(&items);
+            match args.no_headers {
+                true => emit(format!("{}\n", without_header(&table))),
+                false => emit(format!("{}\n", table)),
+            }
+        }
+        (OutputFormat::Table, false) => {
+            let mut table = match args.no_headers {
+                true => new_headless_table(),
+                false => new_table(columns.clone()),
+            };
+            for row in selected_rows {
+                table.add_row(columns.iter().map(|column| row_cell(row, column)));
+            }
+            emit(format!("{}\n", table));
+        }
+        (OutputFormat::Csv, true) => print_csv(&fields, selected_rows, !args.no_headers)?,
+        (OutputFormat::Csv, false) => print_csv(&columns, selected_rows, !args.no_headers)?,
+        (_, true) => {
+            let items: Vec<&T> = selected.iter().map(|&index| &items[index]).collect();
+            print_serialized(format, &items)?
+        }
+        (_, false) => {
+            let rows: Vec<Map<String, Value>> = selected_rows
+                .map(|row| {
+                    columns
+                        .iter()
+                        .map(|column| (column.clone(), row.get(column).cloned().into()))
+                        .collect()
+                })
+                .collect();
+            print_serialized(format, &rows)?
+        }
+    }
+    Ok(())
+}
+
+/// Prints a single item, `text` prints it for people.
+pub fn item<T: Serialize>(
+    format: OutputFormat,
+    item: &T,
+    text: impl FnOnce(&T),
+) -> Result<(), ClientError> {
+    match format {
+        OutputFormat::Table => text(item),
+        OutputFormat::Csv => {
+            let rows = rows(std::slice::from_ref(item))?;
+            print_csv(&fields(&rows), rows.iter(), true)?
+        }
+        _ => print_serialized(format, item)?,
+    }
+    Ok(())
+}
+
+/// Field/value table of an item, for items without a dedicated rendering.
+pub fn fields_table<T: Serialize>(item: &T) -> Result<Table, ClientError> {
+    let mut table = new_table(vec![
+        Message::OutputField.to_string(),
+        Message::OutputValue.to_string(),
+    ]);
+    for row in rows(std::slice::from_ref(item))? {
+        for (field, value) in row {
+            table.add_row(vec![Cell::new(field), Cell::new(cell(&value))]);
+        }
+    }
+    Ok(table)
+}
+
+/// Flattens every item into a single level map, nested fields are named after
+/// their path, e.g. `capabilities.video`.
+pub fn rows<T: Serialize>(items: &[T]) -> Result<Vec<Map<String, Value>>, ClientError> {
+    items
+        .iter()
+        .map(|item| {
+            let mut row = Map::new();
+            flatten("", to_value(item)?, &mut row);
+            Ok(row)
+        })
+        .collect()
+}
+
+/// Text of a flattened value, lists of plain values are joined.
+pub fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(value) => value.clone(),
+        Value::Array(values) if values.iter().all(|value| !value.is_object()) => {
+            values.iter().map(cell).collect::<Vec<String>>().join(", ")
+        }
+        value => value.to_string(),
+    }
+}
+
+fn flatten(prefix: &str, value: Value, row: &mut Map<String, Value>) {
+    match value {
+        Value::Object(fields) => {
+            for (field, value) in fields {
+                match prefix {
+                    "" => flatten(&field, value, row),
+                    _ => flatten(&format!("{}.{}", prefix, field), value, row),
+                }
+            }
+        }
+        value if prefix.is_empty() => {
+            row.insert("value".to_string(), value);
+        }
+        value => {
+            row.insert(prefix.to_string(), value);
+        }
+    }
+}
+
+fn to_value<T: Serialize>(item: &T) -> Result<Value, ClientError> {
+    serde_json::to_value(item).map_err(|_| ClientError::InternalError)
+}
+
+fn print_serialized<T: Serialize + ?Sized>(
+    format: OutputFormat,
+    item: &T,
+) -> Result<(), ClientError> {
+    match format {
+        OutputFormat::Yaml => {
+            let yaml = serde_yaml::to_string(item).map_err(|_| ClientError::InternalError)?;
+            emit(yaml);
+        }
+        _ => {
+            let json =
+                serde_json::to_string_pretty(item).map_err(|_| ClientError::InternalError)?;
+            emit(format!("{}\n", json));
+        }
+    }
+    Ok(())
+}
+
+/// Fields of every row, in order of appearance.
+fn fields(rows: &[Map<String, Value>]) -> Vec<String> {
+    let mut fields: Vec<String> = vec![];
+    for row in rows {
+        for field in row.keys() {
+            if !fields.contains(field) {
+                fields.push(field.clone());
+            }
+        }
+    }
+    fields
+}
+
+/// Fields designated by `name`: the field itself, the fields nested under it,
+/// or else the only field starting with it, or else the only one ending with
+/// `_name`, e.g. `type` for `host_type`.
+fn resolve(name: &str, fields: &[String]) -> Vec<String> {
+    if fields.iter().any(|field| field == name) {
+        return vec![name.to_string()];
+    }
+    let nested: Vec<String> = fields
+        .iter()
+        .filter(|field| field.starts_with(&format!("{}.", name)))
+        .cloned()
+        .collect();
+    if !nested.is_empty() {
+        return nested;
+    }
+    let only = |matches: fn(&str, &str) -> bool| -> Option<String> {
+        match fields
+            .iter()
+            .filter(|field| matches(field, name))
+            .collect::<Vec<&String>>()
+            .as_slice()
+        {
+            [field] => Some(field.to_string()),
+            _ => None,
+        }
+    };
+    only(|field, name| field.starts_with(name))
+        .or_else(|| only(|field, name| field.ends_with(&format!("_{}", name))))
+        .into_iter()
+        .collect()
+}
+
+fn resolve_one(name: &str, fields: &[String]) -> Result<String, ClientError> {
+    match resolve(name, fields).as_slice() {
+        [field] => Ok(field.clone()),
+        _ => Err(unknown_field(name, fields)),
+    }
+}
+
+fn unknown_field(name: &str, fields: &[String]) -> ClientError {
+    eprintln!("{}", Message::ListUnknownField(name, &fields.join(", ")));
+    ClientError::InvalidArgument(Message::ErrorUnknownField(name).to_string())
+}
+
+/// `field=value` or `field!=value`, values are compared without case and
+/// match any element of a list.
+struct Filter {
+    field: String,
+    value: String,
+    negated: bool,
+}
+
+impl Filter {
+    fn parse(filter: &str, fields: &[String]) -> Result<Self, ClientError> {
+        let (field, value, negated) = match filter.split_once("!=") {
+            Some((field, value)) => (field, value, true),
+            None => match filter.split_once('=') {
+                Some((field, value)) => (field, value, false),
+                None => {
+                    return Err(ClientError::InvalidArgument(
+                        Message::ErrorInvalidFilter(filter).to_string(),
+                    ))
+                }
+            },
+        };
+        Ok(Filter {
+            field: resolve_one(field.trim(), fields)?,
+            value: value.trim().to_string(),
+            negated,
+        })
+    }
+
+    fn matches(&self, row: &Map<String, Value>) -> bool {
+        let matches = match row.get(&self.field) {
+            Some(Value::Array(values)) => values
+                .iter()
+                .any(|value| cell(value).eq_ignore_ascii_case(&self.value)),
+            value => value
+                .map(cell)
+                .unwrap_or_default()
+                .eq_ignore_ascii_case(&self.value),
+        };
+        matches != self.negated
+    }
+}
+
+/// Numbers and booleans are compared by value, anything else by text, and
+/// missing values come last.
+fn compare(a: Option<&Value>, b: Option<&Value>) -> Ordering {
+    match (a, b) {
+        (Some(Value::Number(a)), Some(Value::Number(b))) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Some(Value::Bool(a)), Some(Value::Bool(b))) => a.cmp(b),
+        (None | Some(Value::Null), None | Some(Value::Null)) => Ordering::Equal,
+        (None | Some(Value::Null), _) => Ordering::Greater,
+        (_, None | Some(Value::Null)) => Ordering::Less,
+        (Some(a), Some(b)) => cell(a).cmp(&cell(b)),
+    }
+}
+
+fn row_cell(row: &Map<String, Value>, column: &str) -> String {
+    row.get(column).map(cell).unwrap_or_default()
+}
+
+fn without_header(table: &Table) -> Table {
+    let mut headless = new_headless_table();
+    for row in table.row_iter() {
+        headless.add_row(row.clone());
+    }
+    headless
+}
+
+fn print_csv<'a>(
+    columns: &[String],
+    rows: impl Iterator<Item = &'a Map<String, Value>>,
+    headers: bool,
+) -> Result<(), ClientError> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+    let write = || -> Result<(), csv::Error> {
+        if headers {
+            writer.write_record(columns)?;
+        }
+        for row in rows {
+            writer.write_record(columns.iter().map(|column| row_cell(row, column)))?;
+        }
+        writer.flush()?;
+        Ok(())
+    };
+    write().map_err(|_| ClientError::InternalError)?;
+    let csv = writer
+        .into_inner()
+        .map_err(|_| ClientError::InternalError)?;
+    emit(String::from_utf8_lossy(&csv).to_string());
+    Ok(())
+}
+
+/// Writes results on stdout. The tests keep them aside instead, to check
+/// them with [`captured`].
+fn emit(text: String) {
+    #[cfg(not(test))]
+    print!("{}", text);
+    #[cfg(test)]
+    CAPTURED.with(|captured| captured.borrow_mut().push_str(&text));
+}
+
+#[cfg(test)]
+thread_local! {
+    static CAPTURED: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
+}
+
+/// Results written by the current thread since the last call.
+#[cfg(test)]
+pub fn captured() -> String {
+    CAPTURED.with(|captured| captured.take())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn device_fields() -> Vec<String> {
+        [
+            "id",
+            "primary_name",
+            "host_type",
+            "l2ident.id",
+            "l2ident.type",
+            "l3connectivities",
+            "last_activity",
+            "last_time_reachable",
+        ]
+        .iter()
+        .map(|field| field.to_string())
+        .collect()
+    }
+
+    fn row(value: Value) -> Map<String, Value> {
+        rows(&[value]).unwrap().remove(0)
+    }
+
+    #[test]
+    fn fields_resolve_by_name_path_prefix_or_suffix() {
+        let fields = device_fields();
+        assert_eq!(resolve("host_type", &fields), vec!["host_type"]);
+        assert_eq!(
+            resolve("l2ident", &fields),
+            vec!["l2ident.id", "l2ident.type"]
+        );
+        assert_eq!(resolve("l3", &fields), vec!["l3connectivities"]);
+        assert_eq!(resolve("type", &fields), vec!["host_type"]);
+        assert!(resolve("last", &fields).is_empty());
+        assert!(resolve("vendor", &fields).is_empty());
+        assert!(matches!(
+            resolve_one("l2ident", &fields),
+            Err(ClientError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn filters_are_parsed() {
+        let fields = device_fields();
+        let filter = Filter::parse("type=smartphone", &fields).unwrap();
+        assert_eq!(filter.field, "host_type");
+        assert_eq!(filter.value, "smartphone");
+        assert!(!filter.negated);
+        let filter = Filter::parse("l2ident.type != mac_address", &fields).unwrap();
+        assert_eq!(filter.field, "l2ident.type");
+        assert_eq!(filter.value, "mac_address");
+        assert!(filter.negated);
+        assert!(matches!(
+            Filter::parse("smartphone", &fields),
+            Err(ClientError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            Filter::parse("vendor=apple", &fields),
+            Err(ClientError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn filters_match_nested_fields_and_lists() {
+        let fields = vec!["capabilities.video".to_string(), "names".to_string()];
+        let receiver = row(json!({"capabilities": {"video": true}, "names": ["TV", "Salon"]}));
+        assert!(Filter::parse("capabilities.video=TRUE", &fields)
+            .unwrap()
+            .matches(&receiver));
+        assert!(Filter::parse("names=salon", &fields)
+            .unwrap()
+            .matches(&receiver));
+        assert!(!Filter::parse("names!=tv", &fields)
+            .unwrap()
+            .matches(&receiver));
+    }
+
+    #[test]
+    fn numbers_are_compared_by_value_and_text_by_text() {
+        let (two, ten) = (json!(2), json!(10));
+        assert_eq!(compare(Some(&two), Some(&ten)), Ordering::Less);
+        let (two, ten) = (json!("2"), json!("10"));
+        assert_eq!(compare(Some(&two), Some(&ten)), Ordering::Greater);
+        assert_eq!(compare(Some(&Value::Null), Some(&two)), Ordering::Greater);
+        assert_eq!(compare(None, Some(&two)), Ordering::Greater);
+        assert_eq!(
+            compare(Some(&json!(false)), Some(&json!(true))),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn nested_fields_are_flattened() {
+        let row = row(json!({"id": 1, "l2ident": {"id": "aa", "type": "mac_address"}}));
+        assert_eq!(
+            row.keys().collect::<Vec<&String>>(),
+            vec!["id", "l2ident.id", "l2ident.type"]
+        );
+    }
+}
@@ -1,12 +1,38 @@
 use crate::client::HttpClient;
+use crate::models::freebox::air_media::{
+    AirMediaConfigBody, AirMediaReceiverRequest, AirMediaReceiverRequestResponse,
+    GetAirMediaConfigResponse, ListAirMediaReceiversResponse, UpdateAirMediaConfigResponse,
+};
 use crate::models::freebox::authorization::{
     AuthLoginResponse, AuthLogoutResponse, AuthSessionStartRequest, AuthSessionStartResponse,
     AuthTokenRequest, AuthTokenResponse, AuthTrackAuthorizationProgressResponse,
 };
+use crate::models::freebox::connection::{
+    ConnectionConfigurationBody, GetConnectionConfiguration, UpdateConnectionConfiguration,
+};
+use crate::models::freebox::dhcp::{ListDHCPDynamicLeasesResponse, ListDHCPStaticLeasesResponse};
+use crate::models::freebox::lan::ListLanResponse;
+use crate::models::freebox::language::{
+    GetLanguageSupportResponse, LanguageSupportUpdateBody, UpdateLanguageSupportResponse,
+};
+use crate::models::freebox::lcd::{LcdInfoResponse, LcdUpdateBody, LcdUpdateResponse};
+use crate::models::freebox::notification::{
+    DeleteNotificationTargetResponse, ListNotificationTargetsResponse, NotificationSend,
+    NotificationTargetUpdateBody, RegisterNotificationTargetResponse,
+    UpdateNotificationTargetResponse,
+};
+use crate::models::freebox::port_forwarding::ListPortForwardingResponse;
+use crate::models::freebox::system::{SystemInfoResult, SystemPowerResponse};
 use crate::models::freebox::version::VersionResponse;
+use crate::models::freebox::vm::ListVmResponse;
+use crate::models::freebox::wifi::{
+    GetWifiGlobalConfigResponse, ListWifiApResponse, ListWifiBssResponse, UpdateWifiBssResponse,
+    UpdateWifiGlobalConfigResponse, WifiBssUpdateBody, WifiGlobalConfigBody,
+};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use std::collections::HashMap;
 
-const HEADER_FBX_TOKEN: &str = "X-Fbx-App-Auth";
+pub const HEADER_FBX_TOKEN: &str = "X-Fbx-App-Auth";
 
 pub trait FreeboxOSApiCalls<T: HttpClient> {
     async fn api_version(&self, client: &T) -> Result<VersionResponse, T::Error>;
@@ -35,6 +61,155 @@ pub trait FreeboxOSApiCalls<T: HttpClient> {
         client: &T,
         session_token: String,
     ) -> Result<AuthLogoutResponse, T::Error>;
+    async fn system_info(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<SystemInfoResult, T::Error>;
+    async fn system_reboot(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<SystemPowerResponse, T::Error>;
+    async fn system_shutdown(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<SystemPowerResponse, T::Error>;
+    async fn lcd_config(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<LcdInfoResponse, T::Error>;
+    async fn update_lcd_config(
+        &self,
+        client: &T,
+        session_token: String,
+        body: LcdUpdateBody,
+    ) -> Result<LcdUpdateResponse, T::Error>;
+    async fn air_media_config(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<GetAirMediaConfigResponse, T::Error>;
+    async fn update_air_media_config(
+        &self,
+        client: &T,
+        session_token: String,
+        body: AirMediaConfigBody,
+    ) -> Result<UpdateAirMediaConfigResponse, T::Error>;
+    async fn air_media_receivers(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<ListAirMediaReceiversResponse, T::Error>;
+    async fn air_media_receiver_request(
+        &self,
+        client: &T,
+        session_token: String,
+        receiver: &str,
+        body: AirMediaReceiverRequest,
+    ) -> Result<AirMediaReceiverRequestResponse, T::Error>;
+    async fn language(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<GetLanguageSupportResponse, T::Error>;
+    async fn update_language(
+        &self,
+        client: &T,
+        session_token: String,
+        body: LanguageSupportUpdateBody,
+    ) -> Result<UpdateLanguageSupportResponse, T::Error>;
+    async fn notification_targets(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<ListNotificationTargetsResponse, T::Error>;
+    async fn update_notification_target(
+        &self,
+        client: &T,
+        session_token: String,
+        id: &str,
+        body: NotificationTargetUpdateBody,
+    ) -> Result<UpdateNotificationTargetResponse, T::Error>;
+    async fn delete_notification_target(
+        &self,
+        client: &T,
+        session_token: String,
+        id: &str,
+    ) -> Result<DeleteNotificationTargetResponse, T::Error>;
+    async fn register_notification_target(
+        &self,
+        client: &T,
+        session_token: String,
+        body: NotificationSend,
+    ) -> Result<RegisterNotificationTargetResponse, T::Error>;
+    async fn connection_config(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<GetConnectionConfiguration, T::Error>;
+    async fn update_connection_config(
+        &self,
+        client: &T,
+        session_token: String,
+        body: ConnectionConfigurationBody,
+    ) -> Result<UpdateConnectionConfiguration, T::Error>;
+    async fn lan_hosts(
+        &self,
+        client: &T,
+        session_token: String,
+        interface: &str,
+    ) -> Result<ListLanResponse, T::Error>;
+    async fn dhcp_dynamic_leases(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<ListDHCPDynamicLeasesResponse, T::Error>;
+    async fn dhcp_static_leases(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<ListDHCPStaticLeasesResponse, T::Error>;
+    async fn port_forwardings(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<ListPortForwardingResponse, T::Error>;
+    async fn vms(&self, client: &T, session_token: String) -> Result<ListVmResponse, T::Error>;
+    async fn wifi_config(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<GetWifiGlobalConfigResponse, T::Error>;
+    async fn update_wifi_config(
+        &self,
+        client: &T,
+        session_token: String,
+        body: WifiGlobalConfigBody,
+    ) -> Result<UpdateWifiGlobalConfigResponse, T::Error>;
+    async fn wifi_aps(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<ListWifiApResponse, T::Error>;
+    async fn wifi_bss(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<ListWifiBssResponse, T::Error>;
+    async fn update_wifi_bss(
+        &self,
+        client: &T,
+        session_token: String,
+        id: &str,
+        body: WifiBssUpdateBody,
+    ) -> Result<UpdateWifiBssResponse, T::Error>;
+}
+
+fn session_headers(session_token: String) -> HashMap<String, String> {
+    HashMap::from([(String::from(HEADER_FBX_TOKEN), session_token)])
 }
 
 #[derive(Debug, Clone, Default)]
@@ -94,10 +269,340 @@ where
             .post(
                 "/login/logout/",
                 Some(()),
-                Some(HashMap::from([(
-                    String::from(HEADER_FBX_TOKEN),
-                    session_token,
-                )])),
+                Some(session_headers(session_token)),
+            )
+            .await
+    }
+
+    async fn system_info(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<SystemInfoResult, T::Error> {
+        client
+            .get("/system/", Some(session_headers(session_token)))
+            .await
+    }
+
+    async fn system_reboot(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<SystemPowerResponse, T::Error> {
+        client
+            .post(
+                "/system/reboot/",
+                Some(()),
+                Some(session_headers(session_token)),
+            )
+            .await
+    }
+
+    async fn system_shutdown(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<SystemPowerResponse, T::Error> {
+        client
+            .post(
+                "/system/shutdown/",
+                Some(()),
+                Some(session_headers(session_token)),
+            )
+            .await
+    }
+
+    async fn lcd_config(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<LcdInfoResponse, T::Error> {
+        client
+            .get("/lcd/config/", Some(session_headers(session_token)))
+            .await
+    }
+
+    async fn update_lcd_config(
+        &self,
+        client: &T,
+        session_token: String,
+        body: LcdUpdateBody,
+    ) -> Result<LcdUpdateResponse, T::Error> {
+        client
+            .put(
+                "/lcd/config/",
+                Some(body),
+                Some(session_headers(session_token)),
+            )
+            .await
+    }
+
+    async fn air_media_config(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<GetAirMediaConfigResponse, T::Error> {
+        client
+            .get("/airmedia/config/", Some(session_headers(session_token)))
+            .await
+    }
+
+    async fn update_air_media_config(
+        &self,
+        client: &T,
+        session_token: String,
+        body: AirMediaConfigBody,
+    ) -> Result<UpdateAirMediaConfigResponse, T::Error> {
+        client
+            .put(
+                "/airmedia/config/",
+                Some(body),
+                Some(session_headers(session_token)),
+            )
+            .await
+    }
+
+    async fn air_media_receivers(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<ListAirMediaReceiversResponse, T::Error> {
+        client
+            .get("/airmedia/receivers/", Some(session_headers(session_token)))
+            .await
+    }
+
+    async fn air_media_receiver_request(
+        &self,
+        client: &T,
+        session_token: String,
+        receiver: &str,
+        body: AirMediaReceiverRequest,
+    ) -> Result<AirMediaReceiverRequestResponse, T::Error> {
+        let receiver = utf8_percent_encode(receiver, NON_ALPHANUMERIC);
+        client
+            .post(
+                format!("/airmedia/receivers/{}/", receiver).as_str(),
+                Some(body),
+                Some(session_headers(session_token)),
+            )
+            .await
+    }
+
+    async fn language(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<GetLanguageSupportResponse, T::Error> {
+        client
+            .get("/lang/", Some(session_headers(session_token)))
+            .await
+    }
+
+    async fn update_language(
+        &self,
+        client: &T,
+        session_token: String,
+        body: LanguageSupportUpdateBody,
+    ) -> Result<UpdateLanguageSupportResponse, T::Error> {
+        client
+            .post("/lang/", Some(body), Some(session_headers(session_token)))
+            .await
+    }
+
+    async fn notification_targets(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<ListNotificationTargetsResponse, T::Error> {
+        client
+            .get("/notif/targets/", Some(session_headers(session_token)))
+            .await
+    }
+
+    async fn update_notification_target(
+        &self,
+        client: &T,
+        session_token: String,
+        id: &str,
+        body: NotificationTargetUpdateBody,
+    ) -> Result<UpdateNotificationTargetResponse, T::Error> {
+        let id = utf8_percent_encode(id, NON_ALPHANUMERIC);
+        client
+            .put(
+                format!("/notif/targets/{}/", id).as_str(),
+                Some(body),
+                Some(session_headers(session_token)),
+            )
+            .await
+    }
+
+    async fn delete_notification_target(
+        &self,
+        client: &T,
+        session_token: String,
+        id: &str,
+    ) -> Result<DeleteNotificationTargetResponse, T::Error> {
+        let id = utf8_percent_encode(id, NON_ALPHANUMERIC);
+        client
+            .delete(
+                format!("/notif/targets/{}/", id).as_str(),
+                Some(session_headers(session_token)),
+            )
+            .await
+    }
+
+    async fn register_notification_target(
+        &self,
+        client: &T,
+        session_token: String,
+        body: NotificationSend,
+    ) -> Result<RegisterNotificationTargetResponse, T::Error> {
+        client
+            .post(
+                "/notif/targets/",
+                Some(body),
+                Some(session_headers(session_token)),
+            )
+            .await
+    }
+
+    async fn connection_config(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<GetConnectionConfiguration, T::Error> {
+        client
+            .get("/connection/config/", Some(session_headers(session_token)))
+            .await
+    }
+
+    async fn update_connection_config(
+        &self,
+        client: &T,
+        session_token: String,
+        body: ConnectionConfigurationBody,
+    ) -> Result<UpdateConnectionConfiguration, T::Error> {
+        client
+            .put(
+                "/connection/config/",
+                Some(body),
+                Some(session_headers(session_token)),
+            )
+            .await
+    }
+
+    async fn lan_hosts(
+        &self,
+        client: &T,
+        session_token: String,
+        interface: &str,
+    ) -> Result<ListLanResponse, T::Error> {
+        client
+            .get(
+                &format!(
+                    "/lan/browser/{}/",
+                    utf8_percent_encode(interface, NON_ALPHANUMERIC)
+                ),
+                Some(session_headers(session_token)),
+            )
+            .await
+    }
+
+    async fn dhcp_dynamic_leases(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<ListDHCPDynamicLeasesResponse, T::Error> {
+        client
+            .get("/dhcp/dynamic_lease/", Some(session_headers(session_token)))
+            .await
+    }
+
+    async fn dhcp_static_leases(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<ListDHCPStaticLeasesResponse, T::Error> {
+        client
+            .get("/dhcp/static_lease/", Some(session_headers(session_token)))
+            .await
+    }
+
+    async fn port_forwardings(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<ListPortForwardingResponse, T::Error> {
+        client
+            .get("/fw/redir/", Some(session_headers(session_token)))
+            .await
+    }
+
+    async fn vms(&self, client: &T, session_token: String) -> Result<ListVmResponse, T::Error> {
+        client
+            .get("/vm/", Some(session_headers(session_token)))
+            .await
+    }
+
+    async fn wifi_config(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<GetWifiGlobalConfigResponse, T::Error> {
+        client
+            .get("/wifi/config/", Some(session_headers(session_token)))
+            .await
+    }
+
+    async fn update_wifi_config(
+        &self,
+        client: &T,
+        session_token: String,
+        body: WifiGlobalConfigBody,
+    ) -> Result<UpdateWifiGlobalConfigResponse, T::Error> {
+        client
+            .put(
+                "/wifi/config/",
+                Some(body),
+                Some(session_headers(session_token)),
+            )
+            .await
+    }
+
+    async fn wifi_aps(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<ListWifiApResponse, T::Error> {
+        client
+            .get("/wifi/ap/", Some(session_headers(session_token)))
+            .await
+    }
+
+    async fn wifi_bss(
+        &self,
+        client: &T,
+        session_token: String,
+    ) -> Result<ListWifiBssResponse, T::Error> {
+        client
+            .get("/wifi/bss/", Some(session_headers(session_token)))
+            .await
+    }
+
+    async fn update_wifi_bss(
+        &self,
+        client: &T,
+        session_token: String,
+        id: &str,
+        body: WifiBssUpdateBody,
+    ) -> Result<UpdateWifiBssResponse, T::Error> {
+        let id = utf8_percent_encode(id, NON_ALPHANUMERIC);
+        client
+            .put(
+                format!("/wifi/bss/{}/", id).as_str(),
+                Some(body),
+                Some(session_headers(session_token)),
             )
             .await
     }
@@ -1,97 +1,341 @@
-use futures_util::StreamExt;
-use mdns::{Record, RecordKind};
-use std::future::Future;
-use std::pin::Pin;
+use crate::i18n::Message;
+use crate::models::exception::ClientError;
+use crate::models::freebox::version::VersionResponse;
+use futures_util::{pin_mut, stream, StreamExt};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr};
+use std::str::FromStr;
 use std::time::Duration;
 
-// _http._tcp.local
-const SERVICE_NAME: &str = "_airplay._tcp.local";
+const SERVICE_NAME: &str = "_fbx-api._tcp.local";
+const QUERY_INTERVAL: Duration = Duration::from_secs(1);
+const DEFAULT_HOST: &str = "mafreebox.freebox.fr";
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+const PROBE_CONCURRENCY: usize = 64;
+const MIN_SUBNET_PREFIX: u8 = 20;
 
-#[derive(Debug)]
+/// A Freebox found on the network, built from the TXT records it advertises.
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct DeviceInfo {
-    pub box_model: String,
+    pub uid: String,
+    pub api_domain: String,
+    pub https_available: bool,
+    pub https_port: i32,
+    pub api_base_url: String,
+    pub api_version: String,
+    pub device_type: String,
+    pub box_model_name: String,
+    pub address: Option<IpAddr>,
 }
 
-pub trait Device {
-    fn get_info(&self, vec: Vec<String>) -> Option<DeviceInfo>;
+impl DeviceInfo {
+    pub fn from_txt_records<'a>(
+        records: impl Iterator<Item = &'a str>,
+        address: Option<IpAddr>,
+    ) -> Option<Self> {
+        let records: HashMap<&str, &str> = records
+            .filter_map(|record| record.split_once('='))
+            .collect();
+        let get = |key: &str| records.get(key).map(|value| value.to_string());
+        Some(DeviceInfo {
+            uid: get("uid")?,
+            api_domain: get("api_domain").unwrap_or_default(),
+            https_available: records.get("https_available") == Some(&"1"),
+            https_port: get("https_port")
+                .and_then(|port| port.parse().ok())
+                .unwrap_or(443),
+            api_base_url: get("api_base_url").unwrap_or_else(|| "/api/".to_string()),
+            api_version: get("api_version").unwrap_or_default(),
+            device_type: get("device_type").unwrap_or_default(),
+            box_model_name: get("box_model_name").unwrap_or_default(),
+            address,
+        })
+    }
+
+    pub fn from_version(version: VersionResponse, address: Option<IpAddr>) -> Self {
+        DeviceInfo {
+            uid: version.uid,
+            api_domain: version.api_domain,
+            https_available: version.https_available,
+            https_port: version.https_port,
+            api_base_url: version.api_base_url,
+            api_version: version.api_version,
+            device_type: version.device_type,
+            box_model_name: version.box_model_name,
+            address,
+        }
+    }
+
+    /// Url of the box on the local network.
+    pub fn url(&self) -> String {
+        match self.address {
+            Some(IpAddr::V4(ip)) => format!("http://{}/", ip),
+            Some(IpAddr::V6(ip)) => format!("http://[{}]/", ip),
+            None => format!("http://{}/", DEFAULT_HOST),
+        }
+    }
 }
 
 pub trait Discover {
-    type DeviceType: Device;
-    async fn discover_device(
-        &self,
-        duration: Duration,
-    ) -> Pin<Box<dyn Future<Output = Vec<Self::DeviceType>>>>;
+    async fn discover_devices(&self, duration: Duration) -> Result<Vec<DeviceInfo>, ClientError>;
 }
 
-pub struct FreeboxDevice;
+/// Browses `_fbx-api._tcp.local` over mDNS, every box answering within the
+/// timeout is returned once.
+pub struct FreeboxDiscover;
 
-impl Device for FreeboxDevice {
-    fn get_info(&self, _: Vec<String>) -> Option<DeviceInfo> {
-        Some(DeviceInfo {
-            box_model: "Freebox Delta".to_string(),
+impl Discover for FreeboxDiscover {
+    async fn discover_devices(&self, duration: Duration) -> Result<Vec<DeviceInfo>, ClientError> {
+        let stream = mdns::discover::all(SERVICE_NAME, QUERY_INTERVAL)
+            .map_err(|_| ClientError::DiscoveryFailed(Message::ErrorMdnsUnavailable.to_string()))?
+            .listen();
+        pin_mut!(stream);
+        let mut devices: Vec<DeviceInfo> = vec![];
+        let _ = tokio::time::timeout(duration, async {
+            while let Some(response) = stream.next().await {
+                let Ok(response) = response else {
+                    continue;
+                };
+                let Some(device) =
+                    DeviceInfo::from_txt_records(response.txt_records(), response.ip_addr())
+                else {
+                    continue;
+                };
+                match devices.iter_mut().find(|known| known.uid == device.uid) {
+                    Some(known) => known.address = known.address.or(device.address),
+                    None => devices.push(device),
+                }
+            }
         })
+        .await;
+        Ok(devices)
     }
 }
 
-pub struct FreeboxDiscover;
+/// An IPv4 network in CIDR notation, e.g. `192.168.1.0/24`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Subnet {
+    pub network: Ipv4Addr,
+    pub prefix: u8,
+}
 
-impl Discover for FreeboxDiscover {
-    type DeviceType = FreeboxDevice;
-
-    async fn discover_device(
-        &self,
-        duration: Duration,
-    ) -> Pin<Box<dyn Future<Output = Vec<Self::DeviceType>>>> {
-        Box::pin(async move {
-            let stream = mdns::discover::all(SERVICE_NAME, duration)
-                .unwrap()
-                .listen();
-            let _a = stream
-                .all(|x| async {
-                    if let Ok(response) = x {
-                        let _addr = response.records().filter_map(to_ip_addr).next();
-                        println!("abc");
-                        true
-                    } else {
-                        false
-                    }
-                })
-                .await; // keep the stream alive
-            vec![FreeboxDevice]
+impl Subnet {
+    /// Every host address of the network, without the network and broadcast
+    /// addresses.
+    pub fn hosts(&self) -> impl Iterator<Item = Ipv4Addr> {
+        let mask = u32::MAX.checked_shl(32 - self.prefix as u32).unwrap_or(0);
+        let network = u32::from(self.network) & mask;
+        let broadcast = network | !mask;
+        let (first, last) = if self.prefix >= 31 {
+            (network, broadcast)
+        } else {
+            (network + 1, broadcast - 1)
+        };
+        (first..=last).map(Ipv4Addr::from)
+    }
+}
+
+impl FromStr for Subnet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Message::DiscoverInvalidSubnet(MIN_SUBNET_PREFIX).to_string();
+        let (network, prefix) = s.split_once('/').unwrap_or((s, "24"));
+        let network = network.parse::<Ipv4Addr>().map_err(|_| invalid())?;
+        let prefix = prefix.parse::<u8>().map_err(|_| invalid())?;
+        if !(MIN_SUBNET_PREFIX..=32).contains(&prefix) {
+            return Err(invalid());
+        }
+        Ok(Subnet { network, prefix })
+    }
+}
+
+/// Fallback for networks where multicast is blocked (Docker, VPN, ...): asks
+/// `/api_version` to the default Freebox host, the default gateway and every
+/// host of an optional subnet.
+pub struct HttpProbeDiscover {
+    pub subnet: Option<Subnet>,
+}
+
+impl HttpProbeDiscover {
+    fn candidates(&self) -> Vec<(String, Option<IpAddr>)> {
+        let gateway = default_gateway();
+        let addresses = gateway.into_iter().chain(
+            self.subnet
+                .iter()
+                .flat_map(|subnet| subnet.hosts())
+                .filter(|ip| Some(*ip) != gateway),
+        );
+        std::iter::once((DEFAULT_HOST.to_string(), None))
+            .chain(addresses.map(|ip| (ip.to_string(), Some(IpAddr::V4(ip)))))
+            .collect()
+    }
+}
+
+impl Discover for HttpProbeDiscover {
+    async fn discover_devices(&self, duration: Duration) -> Result<Vec<DeviceInfo>, ClientError> {
+        let client = reqwest::Client::builder()
+            .timeout(PROBE_TIMEOUT)
+            .build()
+            .map_err(|_| ClientError::BuilderError)?;
+        let probes = stream::iter(self.candidates())
+            .map(|(host, address)| {
+                let client = &client;
+                async move {
+                    let version = client
+                        .get(format!("http://{}/api_version", host))
+                        .send()
+                        .await
+                        .ok()?
+                        .json::<VersionResponse>()
+                        .await
+                        .ok()?;
+                    Some(DeviceInfo::from_version(version, address))
+                }
+            })
+            .buffer_unordered(PROBE_CONCURRENCY);
+        pin_mut!(probes);
+        let mut devices: Vec<DeviceInfo> = vec![];
+        let _ = tokio::time::timeout(duration, async {
+            while let Some(probe) = probes.next().await {
+                let Some(device) = probe else {
+                    continue;
+                };
+                match devices.iter_mut().find(|known| known.uid == device.uid) {
+                    Some(known) => known.address = known.address.or(device.address),
+                    None => devices.push(device),
+                }
+            }
         })
+        .await;
+        Ok(devices)
     }
 }
 
-fn to_ip_addr(record: &Record) -> Option<()> {
-    match &record.kind {
-        RecordKind::A(_addr) => (),
-        RecordKind::AAAA(_addr) => (),
-        RecordKind::TXT(x) => {
-            println!("TXT {:?}", x);
+/// Reads the IPv4 default gateway from the kernel routing table, only
+/// available on Linux.
+fn default_gateway() -> Option<Ipv4Addr> {
+    let routes = std::fs::read_to_string("/proc/net/route").ok()?;
+    routes.lines().skip(1).find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields.as_slice() {
+            [_, "00000000", gateway, ..] => u32::from_str_radix(gateway, 16)
+                .ok()
+                .map(|gateway| Ipv4Addr::from(gateway.to_le_bytes())),
+            _ => None,
         }
-        RecordKind::SRV {
-            port,
-            target,
-            weight,
-            priority,
-        } => {
-            println!("SRV {:?}", target);
-            println!("SRV {:?}", weight);
-            println!("SRV {:?}", port);
-            println!("SRV {:?}", priority);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeviceInfo, HttpProbeDiscover, Subnet, DEFAULT_HOST};
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    const RECORDS: [&str; 9] = [
+        "uid=23b86ec8091013d668829fe12791fdab",
+        "api_domain=x3mcu3ka.fbxos.fr",
+        "https_available=1",
+        "https_port=3615",
+        "api_base_url=/api/",
+        "api_version=10.2",
+        "device_type=FreeboxServer7,1",
+        "box_model_name=Freebox v7 (r1)",
+        "ignored",
+    ];
+
+    #[test]
+    fn device_from_txt_records() {
+        let address = Some(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 254)));
+        let device = DeviceInfo::from_txt_records(RECORDS.into_iter(), address).unwrap();
+
+        assert_eq!(device.uid, "23b86ec8091013d668829fe12791fdab");
+        assert_eq!(device.api_domain, "x3mcu3ka.fbxos.fr");
+        assert!(device.https_available);
+        assert_eq!(device.https_port, 3615);
+        assert_eq!(device.api_version, "10.2");
+        assert_eq!(device.device_type, "FreeboxServer7,1");
+        assert_eq!(device.box_model_name, "Freebox v7 (r1)");
+        assert_eq!(device.url(), "http://192.168.1.254/");
+    }
+
+    #[test]
+    fn device_from_txt_records_defaults() {
+        let device = DeviceInfo::from_txt_records(["uid=1"].into_iter(), None).unwrap();
+
+        assert!(!device.https_available);
+        assert_eq!(device.https_port, 443);
+        assert_eq!(device.api_base_url, "/api/");
+        assert_eq!(device.url(), format!("http://{}/", DEFAULT_HOST));
+
+        let address = Some(IpAddr::V6(Ipv6Addr::LOCALHOST));
+        let device = DeviceInfo::from_txt_records(["uid=1"].into_iter(), address).unwrap();
+        assert_eq!(device.url(), "http://[::1]/");
+    }
+
+    #[test]
+    fn device_needs_a_uid() {
+        let records = RECORDS.into_iter().skip(1);
+
+        assert_eq!(DeviceInfo::from_txt_records(records, None), None);
+    }
+
+    #[test]
+    fn subnet_parse() {
+        let subnet: Subnet = "192.168.1.0".parse().unwrap();
+        assert_eq!(subnet.network, Ipv4Addr::new(192, 168, 1, 0));
+        assert_eq!(subnet.prefix, 24);
+        assert_eq!("10.0.0.0/20".parse::<Subnet>().unwrap().prefix, 20);
+
+        for invalid in [
+            "10.0.0.0/8",
+            "10.0.0.0/33",
+            "10.0.0/24",
+            "box/24",
+            "10.0.0.0/x",
+        ] {
+            assert!(invalid.parse::<Subnet>().is_err(), "{}", invalid);
         }
-        RecordKind::NS(name) => println!("NS {:?}", name),
-        RecordKind::MX {
-            preference,
-            exchange,
-        } => {
-            println!("MX {:?}", preference);
-            println!("MX {:?}", exchange);
+    }
+
+    #[test]
+    fn subnet_hosts() {
+        let hosts: Vec<Ipv4Addr> = "192.168.1.42/24"
+            .parse::<Subnet>()
+            .unwrap()
+            .hosts()
+            .collect();
+        assert_eq!(hosts.len(), 254);
+        assert_eq!(hosts[0], Ipv4Addr::new(192, 168, 1, 1));
+        assert_eq!(hosts[253], Ipv4Addr::new(192, 168, 1, 254));
+
+        let hosts: Vec<Ipv4Addr> = "10.0.0.8/31".parse::<Subnet>().unwrap().hosts().collect();
+        assert_eq!(
+            hosts,
+            vec![Ipv4Addr::new(10, 0, 0, 8), Ipv4Addr::new(10, 0, 0, 9)]
+        );
+
+        let hosts: Vec<Ipv4Addr> = "10.0.0.8/32".parse::<Subnet>().unwrap().hosts().collect();
+        assert_eq!(hosts, vec![Ipv4Addr::new(10, 0, 0, 8)]);
+    }
+
+    #[test]
+    fn probes_the_default_host_first_and_the_subnet_once() {
+        let probe = HttpProbeDiscover {
+            subnet: Some("192.168.1.0/30".parse().unwrap()),
+        };
+
+        let candidates = probe.candidates();
+
+        assert_eq!(candidates[0], (DEFAULT_HOST.to_string(), None));
+        for host in [1, 2] {
+            let ip = Ipv4Addr::new(192, 168, 1, host);
+            let probes = candidates
+                .iter()
+                .filter(|(_, address)| *address == Some(IpAddr::V4(ip)))
+                .count();
+            assert_eq!(probes, 1);
         }
-        RecordKind::CNAME(name) => println!("CNAME {:?}", name),
-        RecordKind::PTR(name) => println!("PTR {:?}", name),
-        _ => (),
-    };
-    Some(())
+    }
 }